@@ -1,6 +1,7 @@
 use clap::{Arg, ArgMatches, Command as ClapCommand};
 
 use super::Command;
+use crate::entity::options::RunOptions;
 use crate::entity::project::Project;
 
 const ALL_COMMAND: &str = "all";
@@ -23,10 +24,11 @@ impl<P: Project> Command for AllCommand<P> {
     fn check(&mut self, matches: &ArgMatches) -> Option<()> {
         let matches = matches.subcommand_matches(ALL_COMMAND)?;
         let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        let options = RunOptions::default();
         self.project.gen(proj_dir).unwrap();
-        self.project.val(proj_dir).unwrap();
-        self.project.refgen(proj_dir).unwrap();
-        self.project.test(proj_dir).unwrap();
+        self.project.val(proj_dir, &options).unwrap();
+        self.project.refgen(proj_dir, &options).unwrap();
+        self.project.test(proj_dir, &options).unwrap();
         Some(())
     }
 }
@@ -43,13 +45,13 @@ mod tests {
         fn gen(&mut self, _proj_dir: &str) -> Result<()> {
             Ok(())
         }
-        fn refgen(&mut self, _proj_dir: &str) -> Result<()> {
+        fn refgen(&mut self, _proj_dir: &str, _options: &RunOptions) -> Result<()> {
             Ok(())
         }
-        fn val(&mut self, _proj_dir: &str) -> Result<()> {
+        fn val(&mut self, _proj_dir: &str, _options: &RunOptions) -> Result<()> {
             Ok(())
         }
-        fn test(&mut self, _proj_dir: &str) -> Result<()> {
+        fn test(&mut self, _proj_dir: &str, _options: &RunOptions) -> Result<()> {
             Ok(())
         }
     }
@@ -0,0 +1,86 @@
+use clap::{Arg, ArgMatches, Command as ClapCommand};
+
+use super::Command;
+use crate::entity::project::Project;
+
+const CONSTANTS_COMMAND: &str = "constants";
+
+pub struct ConstantsCommand<P> {
+    pub project: P,
+}
+
+impl<P: Project> Command for ConstantsCommand<P> {
+    fn get_subcommand(&self) -> ClapCommand {
+        ClapCommand::new(CONSTANTS_COMMAND)
+            .about("write the constants in creo.toml as source files and substitute them into statements")
+            .arg(
+                Arg::new("PROJECT")
+                    .help("Project directory")
+                    .required(true)
+                    .index(1),
+            )
+            .arg(
+                Arg::new("STATEMENT")
+                    .help("A statement in task/ (e.g. ja.md) to print with the constants substituted. If omitted, all statements are checked")
+                    .required(false)
+                    .index(2),
+            )
+    }
+    fn check(&mut self, matches: &ArgMatches) -> Option<()> {
+        let matches = matches.subcommand_matches(CONSTANTS_COMMAND)?;
+        let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        let statement = matches.get_one::<String>("STATEMENT");
+        self.project
+            .constants(proj_dir, statement.map(|x| x.as_str()))
+            .unwrap();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::Result;
+    use clap::{error::ErrorKind, Command as ClapCommand};
+
+    #[derive(Default)]
+    struct MockProject {
+        statement: Option<Option<String>>,
+    }
+    impl Project for MockProject {
+        fn constants(&mut self, _proj_dir: &str, statement: Option<&str>) -> Result<()> {
+            self.statement = Some(statement.map(|x| x.to_owned()));
+            Ok(())
+        }
+    }
+    #[test]
+    fn constants_command_positive() {
+        let mut constants_command = ConstantsCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "constants", "project_dir", "ja.md"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(constants_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(constants_command.check(&matches), Some(()));
+        assert_eq!(
+            constants_command.project.statement,
+            Some(Some("ja.md".to_owned()))
+        );
+    }
+    #[test]
+    fn constants_command_negative() {
+        let constants_command = ConstantsCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "constants"];
+        let result = ClapCommand::new("problem-creator")
+            .subcommand(constants_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            result.unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+    }
+}
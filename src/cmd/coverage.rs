@@ -0,0 +1,63 @@
+use clap::{Arg, ArgMatches, Command as ClapCommand};
+
+use super::Command;
+use crate::entity::project::Project;
+
+const COVERAGE_COMMAND: &str = "coverage";
+
+pub struct CoverageCommand<P> {
+    pub project: P,
+}
+
+impl<P: Project> Command for CoverageCommand<P> {
+    fn get_subcommand(&self) -> ClapCommand {
+        ClapCommand::new(COVERAGE_COMMAND)
+            .about("report lines of the reference solution that no testcase runs")
+            .arg(
+                Arg::new("PROJECT")
+                    .help("Project directory")
+                    .required(true)
+                    .index(1),
+            )
+    }
+    fn check(&mut self, matches: &ArgMatches) -> Option<()> {
+        let matches = matches.subcommand_matches(COVERAGE_COMMAND)?;
+        let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        self.project.coverage(proj_dir).unwrap();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::Result;
+    use clap::Command as ClapCommand;
+
+    #[derive(Default)]
+    struct MockProject {
+        proj_dir: Option<String>,
+    }
+    impl Project for MockProject {
+        fn coverage(&mut self, proj_dir: &str) -> Result<()> {
+            self.proj_dir = Some(proj_dir.to_owned());
+            Ok(())
+        }
+    }
+    #[test]
+    fn coverage_command_positive() {
+        let mut coverage_command = CoverageCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "coverage", "project_dir"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(coverage_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(coverage_command.check(&matches), Some(()));
+        assert_eq!(
+            coverage_command.project.proj_dir,
+            Some("project_dir".to_owned())
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use clap::{Arg, ArgMatches, Command as ClapCommand};
+
+use super::{add_counterexample_dir_arg, get_counterexample_dir, Command};
+use crate::entity::project::Project;
+
+const MINIMIZE_COMMAND: &str = "minimize";
+const SOL: &str = "SOL";
+const SOL_LONG_ARG: &str = "sol";
+const TESTCASE: &str = "TESTCASE";
+const TESTCASE_LONG_ARG: &str = "testcase";
+
+pub struct MinimizeCommand<P> {
+    pub project: P,
+}
+
+impl<P: Project> Command for MinimizeCommand<P> {
+    fn get_subcommand(&self) -> ClapCommand {
+        add_counterexample_dir_arg(
+            ClapCommand::new(MINIMIZE_COMMAND)
+                .about("shrink an input on which a solution fails")
+                .arg(
+                    Arg::new(SOL)
+                        .long(SOL_LONG_ARG)
+                        .help("The solution's path in creo.toml")
+                        .value_name("SOLUTION")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new(TESTCASE)
+                        .long(TESTCASE_LONG_ARG)
+                        .help("A testcase name or a path to an input file")
+                        .value_name("TESTCASE")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("PROJECT")
+                        .help("Project directory")
+                        .required(true)
+                        .index(1),
+                ),
+        )
+    }
+    fn check(&mut self, matches: &ArgMatches) -> Option<()> {
+        let matches = matches.subcommand_matches(MINIMIZE_COMMAND)?;
+        let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        let solution = matches.get_one::<String>(SOL).unwrap();
+        let testcase = matches.get_one::<String>(TESTCASE).unwrap();
+        let counterexample_dir = get_counterexample_dir(matches);
+        self.project
+            .minimize(proj_dir, solution, testcase, &counterexample_dir)
+            .unwrap();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::Result;
+    use clap::{error::ErrorKind, Command as ClapCommand};
+
+    #[derive(Default)]
+    struct MockProject {
+        minimized: Option<(String, String, String)>,
+    }
+    impl Project for MockProject {
+        fn minimize(
+            &mut self,
+            _proj_dir: &str,
+            solution: &str,
+            testcase: &str,
+            counterexample_dir: &str,
+        ) -> Result<()> {
+            self.minimized = Some((
+                solution.to_owned(),
+                testcase.to_owned(),
+                counterexample_dir.to_owned(),
+            ));
+            Ok(())
+        }
+    }
+    #[test]
+    fn minimize_command_positive() {
+        let mut minimize_command = MinimizeCommand {
+            project: MockProject::default(),
+        };
+        let command = vec![
+            "problem-creator",
+            "minimize",
+            "project_dir",
+            "--sol",
+            "wa.cpp",
+            "--testcase",
+            "random-01.txt",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(minimize_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(minimize_command.check(&matches), Some(()));
+        assert_eq!(
+            minimize_command.project.minimized,
+            Some((
+                "wa.cpp".to_owned(),
+                "random-01.txt".to_owned(),
+                "counterexamples".to_owned()
+            )),
+        );
+
+        let command = vec![
+            "problem-creator",
+            "minimize",
+            "project_dir",
+            "--sol",
+            "wa.cpp",
+            "--testcase",
+            "found/stress-3.txt",
+            "--counterexample-dir",
+            "found",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(minimize_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(minimize_command.check(&matches), Some(()));
+        assert_eq!(
+            minimize_command.project.minimized,
+            Some((
+                "wa.cpp".to_owned(),
+                "found/stress-3.txt".to_owned(),
+                "found".to_owned()
+            )),
+        );
+    }
+
+    #[test]
+    fn minimize_command_negative() {
+        let minimize_command = MinimizeCommand {
+            project: MockProject::default(),
+        };
+
+        // missing testcase
+        let command = vec![
+            "problem-creator",
+            "minimize",
+            "project_dir",
+            "--sol",
+            "wa.cpp",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(minimize_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            matches.err().map(|x| x.kind()),
+            Some(ErrorKind::MissingRequiredArgument),
+        );
+    }
+}
@@ -1,15 +1,145 @@
-use clap::{ArgMatches, Command as ClapCommand};
+use clap::{Arg, ArgAction, ArgMatches, Command as ClapCommand};
+use glob::Pattern;
+
+use crate::entity::options::{RunOptions, DEFAULT_COUNTEREXAMPLE_DIR};
+use crate::run_util::MAX_CPUS;
 
 pub mod add;
 pub mod all;
 pub mod check;
+pub mod constants;
+pub mod coverage;
 pub mod gen;
 pub mod init;
+pub mod minimize;
+pub mod mutate;
 pub mod refgen;
+pub mod run;
+pub mod samples;
+pub mod statement;
+pub mod stats;
+pub mod stress;
 pub mod test;
+pub mod translations;
 pub mod val;
 
 pub trait Command {
     fn get_subcommand(&self) -> ClapCommand;
     fn check(&mut self, matches: &ArgMatches) -> Option<()>;
 }
+
+const SOLUTION_FILTER: &str = "SOLUTION_FILTER";
+const SOLUTION_FILTER_LONG_ARG: &str = "solution";
+const TESTCASE_FILTER: &str = "TESTCASE_FILTER";
+const TESTCASE_FILTER_LONG_ARG: &str = "testcase";
+const ONLY_CHANGED: &str = "ONLY_CHANGED";
+const ONLY_CHANGED_LONG_ARG: &str = "only-changed";
+const JOBS: &str = "JOBS";
+const JOBS_LONG_ARG: &str = "jobs";
+const CPUS: &str = "CPUS";
+const CPUS_LONG_ARG: &str = "cpus";
+const COUNTEREXAMPLE_DIR: &str = "COUNTEREXAMPLE_DIR";
+const COUNTEREXAMPLE_DIR_LONG_ARG: &str = "counterexample-dir";
+
+/// Adds options that select solutions and testcases to process.
+pub fn add_filter_args(command: ClapCommand) -> ClapCommand {
+    command
+        .arg(
+            Arg::new(SOLUTION_FILTER)
+                .long(SOLUTION_FILTER_LONG_ARG)
+                .value_name("GLOB")
+                .help("Process only solutions whose path matches this pattern")
+                .value_parser(Pattern::new)
+                .required(false),
+        )
+        .arg(
+            Arg::new(TESTCASE_FILTER)
+                .long(TESTCASE_FILTER_LONG_ARG)
+                .value_name("GLOB")
+                .help("Process only testcases whose name matches this pattern")
+                .value_parser(Pattern::new)
+                .required(false),
+        )
+        .arg(
+            Arg::new(ONLY_CHANGED)
+                .long(ONLY_CHANGED_LONG_ARG)
+                .help(
+                    "Skip runs whose binary and files are unchanged since they were last recorded",
+                )
+                .action(ArgAction::SetTrue),
+        )
+}
+
+/// Adds options that run solutions on several testcases at the same time.
+pub fn add_parallel_args(command: ClapCommand) -> ClapCommand {
+    command
+        .arg(
+            Arg::new(JOBS)
+                .long(JOBS_LONG_ARG)
+                .short('j')
+                .value_name("N")
+                .help("Run up to N runs at the same time")
+                .value_parser(clap::value_parser!(usize))
+                .required(false),
+        )
+        .arg(
+            Arg::new(CPUS)
+                .long(CPUS_LONG_ARG)
+                .value_name("LIST")
+                .help(
+                    "Run one run on each of these CPUs (e.g. 0,2,3) at the same time, pinned to it",
+                )
+                .value_parser(parse_cpu)
+                .value_delimiter(',')
+                .conflicts_with(JOBS)
+                .required(false),
+        )
+}
+
+/// Adds the option that sets the directory counterexamples are saved in.
+pub fn add_counterexample_dir_arg(command: ClapCommand) -> ClapCommand {
+    command.arg(
+        Arg::new(COUNTEREXAMPLE_DIR)
+            .long(COUNTEREXAMPLE_DIR_LONG_ARG)
+            .help("The directory counterexamples are saved in, relative to the project directory")
+            .value_name("DIR")
+            .default_value(DEFAULT_COUNTEREXAMPLE_DIR),
+    )
+}
+
+/// Reads the option added by add_counterexample_dir_arg.
+pub fn get_counterexample_dir(matches: &ArgMatches) -> String {
+    matches
+        .get_one::<String>(COUNTEREXAMPLE_DIR)
+        .unwrap()
+        .clone()
+}
+
+// Parses a CPU number given to --cpus.
+fn parse_cpu(s: &str) -> Result<usize, String> {
+    let cpu: usize = s.parse().map_err(|e| format!("{}", e))?;
+    if cpu >= MAX_CPUS {
+        return Err(format!("must be less than {}", MAX_CPUS));
+    }
+    Ok(cpu)
+}
+
+/// Reads options added by add_filter_args and add_parallel_args.
+/// Options that were not added get their default values.
+pub fn get_run_options(matches: &ArgMatches) -> RunOptions {
+    fn get<T: Clone + Send + Sync + 'static>(matches: &ArgMatches, id: &str) -> Option<T> {
+        matches.try_get_one::<T>(id).ok().flatten().cloned()
+    }
+    RunOptions {
+        solution: get(matches, SOLUTION_FILTER),
+        testcase: get(matches, TESTCASE_FILTER),
+        only_changed: get(matches, ONLY_CHANGED).unwrap_or_default(),
+        jobs: get(matches, JOBS).unwrap_or_default(),
+        cpus: matches
+            .try_get_many::<usize>(CPUS)
+            .ok()
+            .flatten()
+            .map_or_else(Vec::new, |cpus| cpus.copied().collect()),
+        ..Default::default()
+    }
+}
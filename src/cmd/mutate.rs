@@ -0,0 +1,115 @@
+use clap::{Arg, ArgMatches, Command as ClapCommand};
+
+use super::{add_parallel_args, get_run_options, Command};
+use crate::entity::project::Project;
+
+const MUTATE_COMMAND: &str = "mutate";
+const MAX_MUTANTS: &str = "MAX_MUTANTS";
+const MAX_MUTANTS_LONG_ARG: &str = "max-mutants";
+
+pub struct MutateCommand<P> {
+    pub project: P,
+}
+
+impl<P: Project> Command for MutateCommand<P> {
+    fn get_subcommand(&self) -> ClapCommand {
+        add_parallel_args(
+            ClapCommand::new(MUTATE_COMMAND)
+                .about("check if the testcases catch mutants of the reference solution")
+                .arg(
+                    Arg::new(MAX_MUTANTS)
+                        .long(MAX_MUTANTS_LONG_ARG)
+                        .help("Try at most N mutants")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(usize))
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("PROJECT")
+                        .help("Project directory")
+                        .required(true)
+                        .index(1),
+                ),
+        )
+    }
+    fn check(&mut self, matches: &ArgMatches) -> Option<()> {
+        let matches = matches.subcommand_matches(MUTATE_COMMAND)?;
+        let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        let max_mutants = matches.get_one::<usize>(MAX_MUTANTS).copied();
+        let options = get_run_options(matches);
+        self.project
+            .mutate(proj_dir, max_mutants, &options)
+            .unwrap();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::entity::options::RunOptions;
+    use crate::error::Result;
+    use clap::{error::ErrorKind, Command as ClapCommand};
+
+    #[derive(Default)]
+    struct MockProject {
+        mutated: Option<(Option<usize>, RunOptions)>,
+    }
+    impl Project for MockProject {
+        fn mutate(
+            &mut self,
+            _proj_dir: &str,
+            max_mutants: Option<usize>,
+            options: &RunOptions,
+        ) -> Result<()> {
+            self.mutated = Some((max_mutants, options.clone()));
+            Ok(())
+        }
+    }
+    #[test]
+    fn mutate_command_positive() {
+        let mut mutate_command = MutateCommand {
+            project: MockProject::default(),
+        };
+        let command = vec![
+            "problem-creator",
+            "mutate",
+            "project_dir",
+            "--max-mutants",
+            "100",
+            "-j",
+            "4",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(mutate_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(mutate_command.check(&matches), Some(()));
+        let (max_mutants, options) = mutate_command.project.mutated.unwrap();
+        assert_eq!(max_mutants, Some(100));
+        assert_eq!(options.workers(), 4);
+    }
+
+    #[test]
+    fn mutate_command_negative() {
+        let mutate_command = MutateCommand {
+            project: MockProject::default(),
+        };
+
+        // invalid number
+        let command = vec![
+            "problem-creator",
+            "mutate",
+            "project_dir",
+            "--max-mutants",
+            "many",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(mutate_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            matches.err().map(|x| x.kind()),
+            Some(ErrorKind::ValueValidation),
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use clap::{Arg, ArgMatches, Command as ClapCommand};
 
-use super::Command;
+use super::{add_filter_args, add_parallel_args, get_run_options, Command};
 use crate::entity::project::Project;
 
 const REFGEN_COMMAND: &str = "refgen";
@@ -13,15 +13,17 @@ pub struct RefGenCommand<P> {
 
 impl<P: Project> Command for RefGenCommand<P> {
     fn get_subcommand(&self) -> ClapCommand {
-        ClapCommand::new(REFGEN_COMMAND)
-            .about("generate test output from a model solution")
-            .arg(Arg::new(SKIP_IN).long(SKIP_IN_LONG_ARG).required(false))
-            .arg(
-                Arg::new("PROJECT")
-                    .help("Project directory")
-                    .required(true)
-                    .index(1),
-            )
+        add_parallel_args(add_filter_args(
+            ClapCommand::new(REFGEN_COMMAND)
+                .about("generate test output from a model solution")
+                .arg(Arg::new(SKIP_IN).long(SKIP_IN_LONG_ARG).required(false))
+                .arg(
+                    Arg::new("PROJECT")
+                        .help("Project directory")
+                        .required(true)
+                        .index(1),
+                ),
+        ))
     }
     fn check(&mut self, matches: &ArgMatches) -> Option<()> {
         let matches = matches.subcommand_matches(REFGEN_COMMAND)?;
@@ -35,7 +37,8 @@ impl<P: Project> Command for RefGenCommand<P> {
         } else {
             self.project.gen(proj_dir).unwrap();
         }
-        self.project.refgen(proj_dir).unwrap();
+        let options = get_run_options(matches);
+        self.project.refgen(proj_dir, &options).unwrap();
         Some(())
     }
 }
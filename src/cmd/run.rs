@@ -0,0 +1,117 @@
+use clap::{Arg, ArgMatches, Command as ClapCommand};
+
+use super::Command;
+use crate::entity::project::Project;
+
+const RUN_COMMAND: &str = "run";
+
+pub struct RunCommand<P> {
+    pub project: P,
+}
+
+impl<P: Project> Command for RunCommand<P> {
+    fn get_subcommand(&self) -> ClapCommand {
+        ClapCommand::new(RUN_COMMAND)
+            .about("run a solution on a single input")
+            .arg(
+                Arg::new("PROJECT")
+                    .help("Project directory")
+                    .required(true)
+                    .index(1),
+            )
+            .arg(
+                Arg::new("SOLUTION")
+                    .help("The solution's path in creo.toml")
+                    .required(true)
+                    .index(2),
+            )
+            .arg(
+                Arg::new("INPUT")
+                    .help("A testcase name or a path to an input file. If omitted, the standard input is used")
+                    .required(false)
+                    .index(3),
+            )
+    }
+    fn check(&mut self, matches: &ArgMatches) -> Option<()> {
+        let matches = matches.subcommand_matches(RUN_COMMAND)?;
+        let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        let solution = matches.get_one::<String>("SOLUTION").unwrap();
+        let input = matches.get_one::<String>("INPUT");
+        self.project
+            .run_solution(proj_dir, solution, input.map(|x| x.as_str()))
+            .unwrap();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::Result;
+    use clap::{error::ErrorKind, Command as ClapCommand};
+
+    #[derive(Default)]
+    struct MockProject {
+        input: Option<Option<String>>,
+    }
+    impl Project for MockProject {
+        fn run_solution(
+            &mut self,
+            _proj_dir: &str,
+            _solution: &str,
+            input: Option<&str>,
+        ) -> Result<()> {
+            self.input = Some(input.map(|x| x.to_owned()));
+            Ok(())
+        }
+    }
+    #[test]
+    fn run_command_positive() {
+        let mut run_command = RunCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "run", "project_dir", "sol.cpp", "1.txt"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(run_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(run_command.check(&matches), Some(()));
+        assert_eq!(run_command.project.input, Some(Some("1.txt".to_owned())));
+
+        // without an input
+        let mut run_command = RunCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "run", "project_dir", "sol.cpp"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(run_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(run_command.check(&matches), Some(()));
+        assert_eq!(run_command.project.input, Some(None));
+    }
+
+    #[test]
+    fn run_command_negative() {
+        let mut run_command = RunCommand {
+            project: MockProject::default(),
+        };
+
+        // missing solution
+        let command = vec!["problem-creator", "run", "project_dir"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(run_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            matches.err().map(|x| x.kind()),
+            Some(ErrorKind::MissingRequiredArgument),
+        );
+
+        // not `run`
+        let command = vec!["problem-creator", "test", "project_dir"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(run_command.get_subcommand())
+            .subcommand(ClapCommand::new("test").arg(Arg::new("PROJECT").required(true).index(1)))
+            .get_matches_from(command);
+        assert_eq!(run_command.check(&matches), None);
+    }
+}
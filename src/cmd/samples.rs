@@ -0,0 +1,63 @@
+use clap::{Arg, ArgMatches, Command as ClapCommand};
+
+use super::Command;
+use crate::entity::project::Project;
+
+const SAMPLES_COMMAND: &str = "samples";
+
+pub struct SamplesCommand<P> {
+    pub project: P,
+}
+
+impl<P: Project> Command for SamplesCommand<P> {
+    fn get_subcommand(&self) -> ClapCommand {
+        ClapCommand::new(SAMPLES_COMMAND)
+            .about("check the samples in statements against the testset and the reference solution")
+            .arg(
+                Arg::new("PROJECT")
+                    .help("Project directory")
+                    .required(true)
+                    .index(1),
+            )
+    }
+    fn check(&mut self, matches: &ArgMatches) -> Option<()> {
+        let matches = matches.subcommand_matches(SAMPLES_COMMAND)?;
+        let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        self.project.check_samples(proj_dir).unwrap();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::Result;
+    use clap::Command as ClapCommand;
+
+    #[derive(Default)]
+    struct MockProject {
+        proj_dir: Option<String>,
+    }
+    impl Project for MockProject {
+        fn check_samples(&mut self, proj_dir: &str) -> Result<()> {
+            self.proj_dir = Some(proj_dir.to_owned());
+            Ok(())
+        }
+    }
+    #[test]
+    fn samples_command_positive() {
+        let mut samples_command = SamplesCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "samples", "project_dir"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(samples_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(samples_command.check(&matches), Some(()));
+        assert_eq!(
+            samples_command.project.proj_dir,
+            Some("project_dir".to_owned())
+        );
+    }
+}
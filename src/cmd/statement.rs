@@ -0,0 +1,63 @@
+use clap::{Arg, ArgMatches, Command as ClapCommand};
+
+use super::Command;
+use crate::entity::project::Project;
+
+const STATEMENT_COMMAND: &str = "statement";
+
+pub struct StatementCommand<P> {
+    pub project: P,
+}
+
+impl<P: Project> Command for StatementCommand<P> {
+    fn get_subcommand(&self) -> ClapCommand {
+        ClapCommand::new(STATEMENT_COMMAND)
+            .about("render the statements in task/ into HTML with samples and limits")
+            .arg(
+                Arg::new("PROJECT")
+                    .help("Project directory")
+                    .required(true)
+                    .index(1),
+            )
+    }
+    fn check(&mut self, matches: &ArgMatches) -> Option<()> {
+        let matches = matches.subcommand_matches(STATEMENT_COMMAND)?;
+        let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        self.project.statement(proj_dir).unwrap();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::Result;
+    use clap::Command as ClapCommand;
+
+    #[derive(Default)]
+    struct MockProject {
+        proj_dir: Option<String>,
+    }
+    impl Project for MockProject {
+        fn statement(&mut self, proj_dir: &str) -> Result<()> {
+            self.proj_dir = Some(proj_dir.to_owned());
+            Ok(())
+        }
+    }
+    #[test]
+    fn statement_command_positive() {
+        let mut statement_command = StatementCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "statement", "project_dir"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(statement_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(statement_command.check(&matches), Some(()));
+        assert_eq!(
+            statement_command.project.proj_dir,
+            Some("project_dir".to_owned())
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use clap::{Arg, ArgMatches, Command as ClapCommand};
+
+use super::Command;
+use crate::entity::project::Project;
+
+const STATS_COMMAND: &str = "stats";
+
+pub struct StatsCommand<P> {
+    pub project: P,
+}
+
+impl<P: Project> Command for StatsCommand<P> {
+    fn get_subcommand(&self) -> ClapCommand {
+        ClapCommand::new(STATS_COMMAND)
+            .about("print the number, sizes and judging time of testcases")
+            .arg(
+                Arg::new("PROJECT")
+                    .help("Project directory")
+                    .required(true)
+                    .index(1),
+            )
+    }
+    fn check(&mut self, matches: &ArgMatches) -> Option<()> {
+        let matches = matches.subcommand_matches(STATS_COMMAND)?;
+        let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        self.project.stats(proj_dir).unwrap();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::Result;
+    use clap::Command as ClapCommand;
+
+    #[derive(Default)]
+    struct MockProject {
+        proj_dir: Option<String>,
+    }
+    impl Project for MockProject {
+        fn stats(&mut self, proj_dir: &str) -> Result<()> {
+            self.proj_dir = Some(proj_dir.to_owned());
+            Ok(())
+        }
+    }
+    #[test]
+    fn stats_command_positive() {
+        let mut stats_command = StatsCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "stats", "project_dir"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(stats_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(stats_command.check(&matches), Some(()));
+        assert_eq!(
+            stats_command.project.proj_dir,
+            Some("project_dir".to_owned())
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use clap::{error::ErrorKind, Arg, ArgAction, ArgMatches, Command as ClapCommand};
+
+use super::{add_counterexample_dir_arg, get_counterexample_dir, Command};
+use crate::entity::options::StressOptions;
+use crate::entity::project::Project;
+
+const STRESS_COMMAND: &str = "stress";
+const GEN: &str = "GEN";
+const GEN_LONG_ARG: &str = "gen";
+const SOL: &str = "SOL";
+const SOL_LONG_ARG: &str = "sol";
+const SEED: &str = "SEED";
+const SEED_LONG_ARG: &str = "seed";
+const ITERATIONS: &str = "ITERATIONS";
+const ITERATIONS_LONG_ARG: &str = "iterations";
+
+pub struct StressCommand<P> {
+    pub project: P,
+}
+
+impl<P: Project> Command for StressCommand<P> {
+    fn get_subcommand(&self) -> ClapCommand {
+        add_counterexample_dir_arg(
+            ClapCommand::new(STRESS_COMMAND)
+            .about("compare two solutions on random inputs until they disagree")
+            .arg(
+                Arg::new(GEN)
+                    .long(GEN_LONG_ARG)
+                    .help("A generator that prints an input, and its arguments. A seed is appended to the arguments")
+                    .value_names(["GENERATOR", "ARGS"])
+                    .num_args(1..)
+                    .required(true),
+            )
+            .arg(
+                Arg::new(SOL)
+                    .long(SOL_LONG_ARG)
+                    .help("A solution to compare, as written in creo.toml. If given once, the solution is compared with the reference solution")
+                    .value_name("SOLUTION")
+                    .action(ArgAction::Append)
+                    .required(true),
+            )
+            .arg(
+                Arg::new(SEED)
+                    .long(SEED_LONG_ARG)
+                    .help("The seed for the first input")
+                    .value_parser(clap::value_parser!(u64))
+                    .default_value("1"),
+            )
+            .arg(
+                Arg::new(ITERATIONS)
+                    .long(ITERATIONS_LONG_ARG)
+                    .help("Give up after this many inputs. By default, inputs are generated until a counterexample is found")
+                    .value_name("N")
+                    .value_parser(clap::value_parser!(u64))
+                    .required(false),
+            )
+            .arg(
+                Arg::new("PROJECT")
+                    .help("Project directory")
+                    .required(true)
+                    .index(1),
+            ),
+        )
+    }
+    fn check(&mut self, matches: &ArgMatches) -> Option<()> {
+        let matches = matches.subcommand_matches(STRESS_COMMAND)?;
+        let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        let solutions: Vec<String> = matches.get_many::<String>(SOL).unwrap().cloned().collect();
+        if solutions.len() > 2 {
+            self.get_subcommand()
+                .error(
+                    ErrorKind::TooManyValues,
+                    format!("--{} can be given at most twice", SOL_LONG_ARG),
+                )
+                .exit();
+        }
+        let options = StressOptions {
+            generator: matches.get_many::<String>(GEN).unwrap().cloned().collect(),
+            solutions,
+            seed: *matches.get_one::<u64>(SEED).unwrap(),
+            iterations: matches.get_one::<u64>(ITERATIONS).copied(),
+            counterexample_dir: get_counterexample_dir(matches),
+        };
+        self.project.stress(proj_dir, &options).unwrap();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::entity::options::DEFAULT_COUNTEREXAMPLE_DIR;
+    use crate::error::Result;
+    use clap::Command as ClapCommand;
+
+    #[derive(Default)]
+    struct MockProject {
+        options: Option<StressOptions>,
+    }
+    impl Project for MockProject {
+        fn stress(&mut self, _proj_dir: &str, options: &StressOptions) -> Result<()> {
+            self.options = Some(options.clone());
+            Ok(())
+        }
+    }
+    #[test]
+    fn stress_command_positive() {
+        let mut stress_command = StressCommand {
+            project: MockProject::default(),
+        };
+        let command = vec![
+            "problem-creator",
+            "stress",
+            "project_dir",
+            "--gen",
+            "gen_random.cpp",
+            "10",
+            "100",
+            "--sol",
+            "sol.cpp",
+            "--sol",
+            "naive.cpp",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(stress_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(stress_command.check(&matches), Some(()));
+        assert_eq!(
+            stress_command.project.options,
+            Some(StressOptions {
+                generator: vec![
+                    "gen_random.cpp".to_owned(),
+                    "10".to_owned(),
+                    "100".to_owned()
+                ],
+                solutions: vec!["sol.cpp".to_owned(), "naive.cpp".to_owned()],
+                seed: 1,
+                iterations: None,
+                counterexample_dir: DEFAULT_COUNTEREXAMPLE_DIR.to_owned(),
+            }),
+        );
+    }
+
+    #[test]
+    fn stress_command_negative() {
+        let stress_command = StressCommand {
+            project: MockProject::default(),
+        };
+
+        // missing --sol
+        let command = vec![
+            "problem-creator",
+            "stress",
+            "project_dir",
+            "--gen",
+            "gen.cpp",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(stress_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            matches.err().map(|x| x.kind()),
+            Some(ErrorKind::MissingRequiredArgument),
+        );
+
+        // invalid seed
+        let command = vec![
+            "problem-creator",
+            "stress",
+            "project_dir",
+            "--gen",
+            "gen.cpp",
+            "--sol",
+            "sol.cpp",
+            "--seed",
+            "x",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(stress_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            matches.err().map(|x| x.kind()),
+            Some(ErrorKind::ValueValidation),
+        );
+    }
+}
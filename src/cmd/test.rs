@@ -1,9 +1,16 @@
-use clap::{Arg, ArgMatches, Command as ClapCommand};
+use clap::{Arg, ArgAction, ArgMatches, Command as ClapCommand};
 
-use super::Command;
+use super::{add_filter_args, add_parallel_args, get_run_options, Command};
+use crate::entity::options::RunOptions;
 use crate::entity::project::Project;
 
 const TEST_COMMAND: &str = "test";
+const SHOW_DIFF: &str = "SHOW_DIFF";
+const SHOW_DIFF_LONG_ARG: &str = "show-diff";
+const STOP_EARLY: &str = "STOP_EARLY";
+const STOP_EARLY_LONG_ARG: &str = "stop-early";
+const CROSS_CHECK: &str = "CROSS_CHECK";
+const CROSS_CHECK_LONG_ARG: &str = "cross-check";
 
 pub struct TestCommand<P> {
     pub project: P,
@@ -11,22 +18,256 @@ pub struct TestCommand<P> {
 
 impl<P: Project> Command for TestCommand<P> {
     fn get_subcommand(&self) -> ClapCommand {
-        ClapCommand::new(TEST_COMMAND)
-            .about("test all solutions")
-            .arg(
-                Arg::new("PROJECT")
-                    .help("Project directory")
-                    .required(true)
-                    .index(1),
-            )
+        add_parallel_args(add_filter_args(
+            ClapCommand::new(TEST_COMMAND)
+                .about("test all solutions")
+                .arg(
+                    Arg::new(SHOW_DIFF)
+                        .long(SHOW_DIFF_LONG_ARG)
+                        .help("Print a unified diff of one solution's output on one testcase")
+                        .num_args(2)
+                        .value_names(["SOLUTION", "TESTCASE"])
+                        .required(false),
+                )
+                .arg(
+                    Arg::new(STOP_EARLY)
+                        .long(STOP_EARLY_LONG_ARG)
+                        .help("Stop running a solution once its overall verdict is settled")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new(CROSS_CHECK)
+                        .long(CROSS_CHECK_LONG_ARG)
+                        .help("Check that all solutions expected to be AC print the same output on each testcase")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with(SHOW_DIFF),
+                )
+                .arg(
+                    Arg::new("PROJECT")
+                        .help("Project directory")
+                        .required(true)
+                        .index(1),
+                ),
+        ))
     }
     fn check(&mut self, matches: &ArgMatches) -> Option<()> {
         let matches = matches.subcommand_matches(TEST_COMMAND)?;
         let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
-        if let Err(e) = self.project.test(proj_dir) {
+        let result = if let Some(values) = matches.get_many::<String>(SHOW_DIFF) {
+            let values: Vec<&String> = values.collect();
+            self.project.show_diff(proj_dir, values[0], values[1])
+        } else if matches.get_flag(CROSS_CHECK) {
+            self.project
+                .cross_check(proj_dir, &get_run_options(matches))
+        } else {
+            let options = RunOptions {
+                stop_early: matches.get_flag(STOP_EARLY),
+                ..get_run_options(matches)
+            };
+            self.project.test(proj_dir, &options)
+        };
+        if let Err(e) = result {
             eprintln!("Error: {}", e);
             panic!("error: e = {:?}", e);
         }
         Some(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::Result;
+    use clap::{error::ErrorKind, Command as ClapCommand};
+
+    #[derive(Default)]
+    struct MockProject {
+        tested: Option<RunOptions>,
+        diffed: Option<(String, String)>,
+        cross_checked: Option<RunOptions>,
+    }
+    impl Project for MockProject {
+        fn test(&mut self, _proj_dir: &str, options: &RunOptions) -> Result<()> {
+            self.tested = Some(options.clone());
+            Ok(())
+        }
+        fn show_diff(&mut self, _proj_dir: &str, solution: &str, testcase: &str) -> Result<()> {
+            self.diffed = Some((solution.to_owned(), testcase.to_owned()));
+            Ok(())
+        }
+        fn cross_check(&mut self, _proj_dir: &str, options: &RunOptions) -> Result<()> {
+            self.cross_checked = Some(options.clone());
+            Ok(())
+        }
+    }
+    #[test]
+    fn test_command_positive() {
+        let mut test_command = TestCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "test", "project_dir"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(test_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(test_command.check(&matches), Some(()));
+        assert_eq!(test_command.project.tested, Some(RunOptions::default()));
+
+        let mut test_command = TestCommand {
+            project: MockProject::default(),
+        };
+        let command = vec![
+            "problem-creator",
+            "test",
+            "project_dir",
+            "--solution",
+            "sol/*-wa.cpp",
+            "--testcase",
+            "random-*",
+            "--only-changed",
+            "--stop-early",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(test_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(test_command.check(&matches), Some(()));
+        let options = test_command.project.tested.unwrap();
+        assert!(options.matches_solution("sol/koba-wa.cpp"));
+        assert!(!options.matches_solution("sol/koba.cpp"));
+        assert!(!options.matches_testcase(std::path::Path::new("sample-1.txt")));
+        assert!(options.only_changed);
+        assert!(options.stop_early);
+        assert_eq!(options.workers(), 1);
+
+        let mut test_command = TestCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "test", "project_dir", "--cpus", "0,2,3"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(test_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(test_command.check(&matches), Some(()));
+        let options = test_command.project.tested.unwrap();
+        assert_eq!(options.cpus, vec![0, 2, 3]);
+        assert_eq!(options.workers(), 3);
+
+        let mut test_command = TestCommand {
+            project: MockProject::default(),
+        };
+        let command = vec![
+            "problem-creator",
+            "test",
+            "project_dir",
+            "--show-diff",
+            "sol/wa.cpp",
+            "1.txt",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(test_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(test_command.check(&matches), Some(()));
+        assert_eq!(test_command.project.tested, None);
+        assert_eq!(
+            test_command.project.diffed,
+            Some(("sol/wa.cpp".to_owned(), "1.txt".to_owned())),
+        );
+
+        let mut test_command = TestCommand {
+            project: MockProject::default(),
+        };
+        let command = vec![
+            "problem-creator",
+            "test",
+            "project_dir",
+            "--cross-check",
+            "-j",
+            "2",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(test_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(test_command.check(&matches), Some(()));
+        assert_eq!(test_command.project.tested, None);
+        let options = test_command.project.cross_checked.unwrap();
+        assert_eq!(options.workers(), 2);
+    }
+
+    #[test]
+    fn test_command_negative() {
+        let test_command = TestCommand {
+            project: MockProject::default(),
+        };
+
+        // --show-diff needs both a solution and a testcase
+        let command = vec![
+            "problem-creator",
+            "test",
+            "project_dir",
+            "--show-diff",
+            "sol/wa.cpp",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(test_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            matches.err().map(|x| x.kind()),
+            Some(ErrorKind::WrongNumberOfValues),
+        );
+
+        // --cross-check and --show-diff cannot be used together
+        let command = vec![
+            "problem-creator",
+            "test",
+            "project_dir",
+            "--cross-check",
+            "--show-diff",
+            "sol/wa.cpp",
+            "1.txt",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(test_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            matches.err().map(|x| x.kind()),
+            Some(ErrorKind::ArgumentConflict),
+        );
+
+        // --jobs and --cpus cannot be used together
+        let command = vec![
+            "problem-creator",
+            "test",
+            "project_dir",
+            "--jobs",
+            "4",
+            "--cpus",
+            "0,1",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(test_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            matches.err().map(|x| x.kind()),
+            Some(ErrorKind::ArgumentConflict),
+        );
+
+        // CPUs out of range
+        let command = vec!["problem-creator", "test", "project_dir", "--cpus", "0,1024"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(test_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            matches.err().map(|x| x.kind()),
+            Some(ErrorKind::ValueValidation),
+        );
+
+        // invalid pattern
+        let command = vec!["problem-creator", "test", "project_dir", "--testcase", "[a"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(test_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            matches.err().map(|x| x.kind()),
+            Some(ErrorKind::ValueValidation),
+        );
+    }
+}
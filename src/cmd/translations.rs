@@ -0,0 +1,90 @@
+use clap::{Arg, ArgAction, ArgMatches, Command as ClapCommand};
+
+use super::Command;
+use crate::entity::project::Project;
+
+const TRANSLATIONS_COMMAND: &str = "translations";
+const ACCEPT: &str = "ACCEPT";
+const ACCEPT_LONG_ARG: &str = "accept";
+
+pub struct TranslationsCommand<P> {
+    pub project: P,
+}
+
+impl<P: Project> Command for TranslationsCommand<P> {
+    fn get_subcommand(&self) -> ClapCommand {
+        ClapCommand::new(TRANSLATIONS_COMMAND)
+            .about("check that the translations of the statement in task/ are consistent")
+            .arg(
+                Arg::new(ACCEPT)
+                    .long(ACCEPT_LONG_ARG)
+                    .help("Record the statements as in sync in task/translations.toml")
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("PROJECT")
+                    .help("Project directory")
+                    .required(true)
+                    .index(1),
+            )
+    }
+    fn check(&mut self, matches: &ArgMatches) -> Option<()> {
+        let matches = matches.subcommand_matches(TRANSLATIONS_COMMAND)?;
+        let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
+        let accept = matches.get_flag(ACCEPT);
+        self.project.check_translations(proj_dir, accept).unwrap();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::Result;
+    use clap::{error::ErrorKind, Command as ClapCommand};
+
+    #[derive(Default)]
+    struct MockProject {
+        accept: Option<bool>,
+    }
+    impl Project for MockProject {
+        fn check_translations(&mut self, _proj_dir: &str, accept: bool) -> Result<()> {
+            self.accept = Some(accept);
+            Ok(())
+        }
+    }
+    #[test]
+    fn translations_command_positive() {
+        let mut translations_command = TranslationsCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "translations", "project_dir"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(translations_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(translations_command.check(&matches), Some(()));
+        assert_eq!(translations_command.project.accept, Some(false));
+
+        let command = vec!["problem-creator", "translations", "--accept", "project_dir"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(translations_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(translations_command.check(&matches), Some(()));
+        assert_eq!(translations_command.project.accept, Some(true));
+    }
+    #[test]
+    fn translations_command_negative() {
+        let translations_command = TranslationsCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "translations", "--accept"];
+        let result = ClapCommand::new("problem-creator")
+            .subcommand(translations_command.get_subcommand())
+            .try_get_matches_from(command);
+        assert_eq!(
+            result.unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+    }
+}
@@ -1,9 +1,14 @@
-use clap::{Arg, ArgMatches, Command as ClapCommand};
+use clap::{Arg, ArgAction, ArgMatches, Command as ClapCommand};
 
-use super::Command;
+use super::{add_filter_args, get_run_options, Command};
+use crate::entity::options::RunOptions;
 use crate::entity::project::Project;
 
 const VAL_COMMAND: &str = "val";
+const FUZZ: &str = "FUZZ";
+const FUZZ_LONG_ARG: &str = "fuzz";
+const REMOVE_DUPLICATES: &str = "REMOVE_DUPLICATES";
+const REMOVE_DUPLICATES_LONG_ARG: &str = "remove-duplicates";
 
 pub struct ValCommand<P> {
     pub project: P,
@@ -11,19 +16,38 @@ pub struct ValCommand<P> {
 
 impl<P: Project> Command for ValCommand<P> {
     fn get_subcommand(&self) -> ClapCommand {
-        ClapCommand::new(VAL_COMMAND)
-            .about("validate testcases (input)")
-            .arg(
-                Arg::new("PROJECT")
-                    .help("Project directory")
-                    .required(true)
-                    .index(1),
-            )
+        add_filter_args(
+            ClapCommand::new(VAL_COMMAND)
+                .about("validate testcases (input)")
+                .arg(
+                    Arg::new(FUZZ)
+                        .long(FUZZ_LONG_ARG)
+                        .help("Also check that validators reject broken copies of the inputs")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new(REMOVE_DUPLICATES)
+                        .long(REMOVE_DUPLICATES_LONG_ARG)
+                        .help("Remove inputs that are the same as another input up to whitespace, and their outputs")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("PROJECT")
+                        .help("Project directory")
+                        .required(true)
+                        .index(1),
+                ),
+        )
     }
     fn check(&mut self, matches: &ArgMatches) -> Option<()> {
         let matches = matches.subcommand_matches(VAL_COMMAND)?;
         let proj_dir = matches.get_one::<String>("PROJECT").unwrap();
-        self.project.val(proj_dir).unwrap();
+        let options = RunOptions {
+            fuzz: matches.get_flag(FUZZ),
+            remove_duplicates: matches.get_flag(REMOVE_DUPLICATES),
+            ..get_run_options(matches)
+        };
+        self.project.val(proj_dir, &options).unwrap();
         Some(())
     }
 }
@@ -35,28 +59,60 @@ mod tests {
     use crate::error::Result;
     use clap::{error::ErrorKind, Command as ClapCommand};
 
-    struct MockProject;
+    #[derive(Default)]
+    struct MockProject {
+        options: Option<RunOptions>,
+    }
     impl Project for MockProject {
-        fn val(&mut self, _proj_dir: &str) -> Result<()> {
+        fn val(&mut self, _proj_dir: &str, options: &RunOptions) -> Result<()> {
+            self.options = Some(options.clone());
             Ok(())
         }
     }
     #[test]
     fn val_command_positive() {
         let mut val_command = ValCommand {
-            project: MockProject,
+            project: MockProject::default(),
         };
         let command = vec!["problem-creator", "val", "project_dir"];
         let matches = ClapCommand::new("problem-creator")
             .subcommand(val_command.get_subcommand())
             .get_matches_from(command);
         assert_eq!(val_command.check(&matches), Some(()));
+        assert_eq!(val_command.project.options, Some(RunOptions::default()));
+
+        let mut val_command = ValCommand {
+            project: MockProject::default(),
+        };
+        let command = vec!["problem-creator", "val", "project_dir", "--fuzz"];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(val_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(val_command.check(&matches), Some(()));
+        let options = val_command.project.options.unwrap();
+        assert!(options.fuzz);
+        assert!(!options.remove_duplicates);
+
+        let mut val_command = ValCommand {
+            project: MockProject::default(),
+        };
+        let command = vec![
+            "problem-creator",
+            "val",
+            "project_dir",
+            "--remove-duplicates",
+        ];
+        let matches = ClapCommand::new("problem-creator")
+            .subcommand(val_command.get_subcommand())
+            .get_matches_from(command);
+        assert_eq!(val_command.check(&matches), Some(()));
+        assert!(val_command.project.options.unwrap().remove_duplicates);
     }
 
     #[test]
     fn val_command_negative() {
         let mut val_command = ValCommand {
-            project: MockProject,
+            project: MockProject::default(),
         };
 
         // unknown arguments
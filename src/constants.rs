@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// A value in the `[constants]` table of creo.toml.
+#[derive(Serialize, Deserialize, Clone, PartialEq, PartialOrd, Debug)]
+#[serde(untagged)]
+pub enum Constant {
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+}
+
+impl std::fmt::Display for Constant {
+    /// Formats the value as it appears in a statement.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Constant::Integer(x) => write!(f, "{}", x),
+            Constant::Float(x) => write!(f, "{}", x),
+            Constant::Bool(x) => write!(f, "{}", x),
+            Constant::String(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+/// Names of the generated files: a C++ header, a Python module and a Rust file.
+pub const CPP_HEADER: &str = "constants.h";
+pub const PYTHON_MODULE: &str = "constants.py";
+pub const RUST_FILE: &str = "constants.rs";
+
+const GENERATED_NOTICE: &str = "Generated by creo from [constants] in creo.toml. Do not edit.";
+
+/// Checks whether a file was generated by `sources`, so that it can be overwritten.
+pub fn is_generated(content: &[u8]) -> bool {
+    ["// ", "# "].iter().any(|comment| {
+        content
+            .strip_prefix(comment.as_bytes())
+            .is_some_and(|rest| rest.starts_with(GENERATED_NOTICE.as_bytes()))
+    })
+}
+
+/// Checks that every name can be used as an identifier in C++, Python and Rust.
+pub fn check_names(constants: &BTreeMap<String, Constant>) -> Result<(), String> {
+    for name in constants.keys() {
+        let mut chars = name.chars();
+        let head_ok = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+        if !head_ok || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("{} is not a valid constant name", name));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the generated files as (file name, content) pairs.
+pub fn sources(constants: &BTreeMap<String, Constant>) -> Vec<(&'static str, String)> {
+    let mut cpp = format!("// {}\n#pragma once\n", GENERATED_NOTICE);
+    let mut python = format!("# {}\n", GENERATED_NOTICE);
+    let mut rust = format!("// {}\n", GENERATED_NOTICE);
+    for (name, value) in constants {
+        let (cpp_decl, python_value, rust_type, value) = match value {
+            Constant::Integer(x) => (
+                format!("long long {}", name),
+                x.to_string(),
+                "i64",
+                x.to_string(),
+            ),
+            // {:?} always has a decimal point or an exponent, so that the value is a float in every language.
+            Constant::Float(x) => (
+                format!("double {}", name),
+                format!("{:?}", x),
+                "f64",
+                format!("{:?}", x),
+            ),
+            Constant::Bool(x) => {
+                let python = if *x { "True" } else { "False" };
+                (
+                    format!("bool {}", name),
+                    python.to_owned(),
+                    "bool",
+                    x.to_string(),
+                )
+            }
+            Constant::String(x) => (format!("char {}[]", name), quote(x), "&str", quote(x)),
+        };
+        writeln!(cpp, "const {} = {};", cpp_decl, value).unwrap();
+        writeln!(python, "{} = {}", name, python_value).unwrap();
+        writeln!(
+            rust,
+            "#[allow(dead_code)]\npub const {}: {} = {};",
+            name, rust_type, value
+        )
+        .unwrap();
+    }
+    vec![
+        (CPP_HEADER, cpp),
+        (PYTHON_MODULE, python),
+        (RUST_FILE, rust),
+    ]
+}
+
+// Quotes a string so that it is a valid string literal in C++, Python and Rust.
+fn quote(s: &str) -> String {
+    let mut quoted = "\"".to_owned();
+    for c in s.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            '\t' => quoted += "\\t",
+            '\r' => quoted += "\\r",
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Replaces every `{{NAME}}` in text with the value of the constant NAME.
+/// Returns the names that are not defined as an error.
+pub fn substitute(
+    text: &str,
+    constants: &BTreeMap<String, Constant>,
+) -> Result<String, Vec<String>> {
+    let mut result = String::new();
+    let mut unknown = vec![];
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        let is_name =
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_name {
+            // Not a placeholder, e.g. braces in TeX. Keep the first brace and look again after it.
+            result += &rest[..start + 1];
+            rest = &rest[start + 1..];
+            continue;
+        }
+        result += &rest[..start];
+        match constants.get(name) {
+            Some(value) => result += &value.to_string(),
+            None => unknown.push(name.to_owned()),
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    result += rest;
+    if unknown.is_empty() {
+        Ok(result)
+    } else {
+        Err(unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constants() -> BTreeMap<String, Constant> {
+        toml::from_str(
+            r#"
+N_MAX = 200000
+EPS = 1e-9
+MULTI = false
+YES = "Yes"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn constants_are_parsed_by_type() {
+        let constants = constants();
+        assert_eq!(constants["N_MAX"], Constant::Integer(200000));
+        assert_eq!(constants["EPS"], Constant::Float(1e-9));
+        assert_eq!(constants["MULTI"], Constant::Bool(false));
+        assert_eq!(constants["YES"], Constant::String("Yes".to_owned()));
+    }
+
+    #[test]
+    fn sources_works() {
+        let sources = sources(&constants());
+        assert_eq!(sources[0].0, CPP_HEADER);
+        assert!(sources[0].1.contains("const long long N_MAX = 200000;\n"));
+        assert!(sources[0].1.contains("const double EPS = 1e-9;\n"));
+        assert!(sources[0].1.contains("const char YES[] = \"Yes\";\n"));
+        assert_eq!(sources[1].0, PYTHON_MODULE);
+        assert!(sources[1].1.contains("MULTI = False\n"));
+        assert_eq!(sources[2].0, RUST_FILE);
+        assert!(sources[2].1.contains("pub const N_MAX: i64 = 200000;\n"));
+        assert!(sources[2].1.contains("pub const YES: &str = \"Yes\";\n"));
+    }
+
+    #[test]
+    fn is_generated_works() {
+        for (_, content) in sources(&constants()) {
+            assert!(is_generated(content.as_bytes()));
+        }
+        assert!(!is_generated(b"const int N_MAX = 10;\n"));
+        assert!(!is_generated(b""));
+    }
+
+    #[test]
+    fn check_names_works() {
+        assert!(check_names(&constants()).is_ok());
+        let mut constants = constants();
+        constants.insert("N-MAX".to_owned(), Constant::Integer(1));
+        assert!(check_names(&constants).is_err());
+    }
+
+    #[test]
+    fn substitute_works() {
+        let constants = constants();
+        assert_eq!(
+            substitute("$1 \\leq N \\leq {{N_MAX}}$, \\frac{{a}+1}{2}", &constants),
+            Ok("$1 \\leq N \\leq 200000$, \\frac{{a}+1}{2}".to_owned()),
+        );
+        assert_eq!(
+            substitute("{{N_MAX}} {{M_MAX}}", &constants),
+            Err(vec!["M_MAX".to_owned()]),
+        );
+    }
+}
@@ -0,0 +1,75 @@
+/// Coverage of one line in a source file.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LineCoverage {
+    /// 1-indexed line number.
+    pub line: usize,
+    /// How many times the line ran. None if the line is not executable.
+    pub count: Option<u64>,
+    /// The content of the line.
+    pub source: String,
+}
+
+/// Reads a coverage report in the text format of `gcov -t`, and returns the coverage of the lines in `source_name`.
+/// A report may contain several source files (e.g. headers); a file matches if its path ends with `source_name`.
+pub fn parse_gcov(report: &str, source_name: &str) -> Vec<LineCoverage> {
+    let mut result = vec![];
+    let mut in_source = false;
+    for line in report.lines() {
+        let mut fields = line.splitn(3, ':');
+        let (Some(count), Some(number), Some(source)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let (count, number) = (count.trim(), number.trim());
+        if number == "0" {
+            if let Some(path) = source.strip_prefix("Source:") {
+                in_source = path.ends_with(source_name);
+            }
+            continue;
+        }
+        let Ok(number) = number.parse() else {
+            continue;
+        };
+        if !in_source {
+            continue;
+        }
+        let count = match count {
+            "-" => None,
+            "#####" | "=====" => Some(0),
+            // Counts of lines that are only partly executed are marked with '*'.
+            count => count.trim_end_matches('*').parse().ok(),
+        };
+        result.push(LineCoverage {
+            line: number,
+            count,
+            source: source.to_owned(),
+        });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_gcov_works() {
+        let report = "        -:    0:Source:/usr/include/c++/12/iostream
+        1:   10:  header();
+        -:    0:Source:/home/user/problem/sol.cpp
+        -:    0:Graph:a-sol.gcno
+        -:    1:#include <iostream>
+        3:    5:int main(void) {
+    #####:    6:  if (n == 0) return 1;
+       2*:    7:  f(); // a:b
+";
+        let lines = parse_gcov(report, "sol.cpp");
+        let counts: Vec<(usize, Option<u64>)> = lines.iter().map(|l| (l.line, l.count)).collect();
+        assert_eq!(
+            counts,
+            vec![(1, None), (5, Some(3)), (6, Some(0)), (7, Some(2))]
+        );
+        assert_eq!(lines[3].source, "  f(); // a:b");
+    }
+}
@@ -0,0 +1,184 @@
+use similar::TextDiff;
+use std::fmt;
+
+/// How many lines before and after a mismatch are shown.
+const CONTEXT_LINES: usize = 2;
+
+/// The first place where an actual output differs from the expected one.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Mismatch {
+    /// 1-indexed line number of the first differing line.
+    pub line: usize,
+    /// 1-indexed position of the first differing token in that line.
+    /// None if the tokens are the same and only whitespace differs.
+    pub token: Option<usize>,
+    /// The expected token. None if the expected line has no such token.
+    pub expected_token: Option<String>,
+    /// The actual token. None if the actual line has no such token.
+    pub actual_token: Option<String>,
+    /// Lines around the mismatch in the expected output, with their line numbers.
+    pub expected_context: Vec<(usize, String)>,
+    /// Lines around the mismatch in the actual output, with their line numbers.
+    pub actual_context: Vec<(usize, String)>,
+}
+
+/// Finds the first mismatch between expected and actual output.
+/// Returns None if they are byte-for-byte equal.
+pub fn first_mismatch<'a>(expected: &'a [u8], actual: &'a [u8]) -> Option<Mismatch> {
+    if expected == actual {
+        return None;
+    }
+    // Lines and tokens are compared as bytes, since outputs that are not valid UTF-8 may become equal
+    // after a lossy conversion. They are converted only to be shown.
+    let expected_lines: Vec<&[u8]> = expected.split(|&b| b == b'\n').collect();
+    let actual_lines: Vec<&[u8]> = actual.split(|&b| b == b'\n').collect();
+    let index = (0..std::cmp::max(expected_lines.len(), actual_lines.len()))
+        .find(|&i| expected_lines.get(i) != actual_lines.get(i))
+        .unwrap_or(0);
+    let tokens = |lines: &[&'a [u8]]| -> Vec<&'a [u8]> {
+        lines
+            .get(index)
+            .map_or(&b""[..], |line| line)
+            .split(|b| b.is_ascii_whitespace())
+            .filter(|token| !token.is_empty())
+            .collect()
+    };
+    let expected_tokens = tokens(&expected_lines);
+    let actual_tokens = tokens(&actual_lines);
+    let token = (0..std::cmp::max(expected_tokens.len(), actual_tokens.len()))
+        .find(|&i| expected_tokens.get(i) != actual_tokens.get(i));
+    let show = |tokens: &[&[u8]], i: usize| {
+        tokens
+            .get(i)
+            .map(|token| String::from_utf8_lossy(token).into_owned())
+    };
+    let (expected_token, actual_token) = match token {
+        Some(i) => (show(&expected_tokens, i), show(&actual_tokens, i)),
+        None => (None, None),
+    };
+    Some(Mismatch {
+        line: index + 1,
+        token: token.map(|i| i + 1),
+        expected_token,
+        actual_token,
+        expected_context: context(&expected_lines, index),
+        actual_context: context(&actual_lines, index),
+    })
+}
+
+fn context(lines: &[&[u8]], index: usize) -> Vec<(usize, String)> {
+    let lo = index.saturating_sub(CONTEXT_LINES);
+    let hi = std::cmp::min(index + CONTEXT_LINES + 1, lines.len());
+    (lo..hi)
+        .map(|i| (i + 1, String::from_utf8_lossy(lines[i]).into_owned()))
+        .collect()
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.token {
+            Some(token) => writeln!(
+                f,
+                "first mismatch at line {}, token {}: expected {}, got {}",
+                self.line,
+                token,
+                show_token(&self.expected_token),
+                show_token(&self.actual_token),
+            )?,
+            None => writeln!(
+                f,
+                "first mismatch at line {}: whitespace differs",
+                self.line,
+            )?,
+        }
+        for (title, lines) in [
+            ("expected", &self.expected_context),
+            ("actual", &self.actual_context),
+        ] {
+            writeln!(f, "  {}:", title)?;
+            for (number, line) in lines {
+                let marker = if *number == self.line { '>' } else { ' ' };
+                writeln!(f, "  {}{:>6} | {}", marker, number, line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn show_token(token: &Option<String>) -> String {
+    match token {
+        Some(token) => format!("`{}`", token),
+        None => "nothing".to_owned(),
+    }
+}
+
+/// Builds a unified diff from expected output to actual output.
+pub fn unified_diff(
+    expected: &[u8],
+    actual: &[u8],
+    expected_name: &str,
+    actual_name: &str,
+) -> String {
+    let expected = String::from_utf8_lossy(expected);
+    let actual = String::from_utf8_lossy(actual);
+    TextDiff::from_lines(expected.as_ref(), actual.as_ref())
+        .unified_diff()
+        .context_radius(3)
+        .header(expected_name, actual_name)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_mismatch_returns_none_on_equal_output() {
+        assert_eq!(first_mismatch(b"1 2\n3\n", b"1 2\n3\n"), None);
+    }
+
+    #[test]
+    fn first_mismatch_finds_token() {
+        let mismatch = first_mismatch(b"a\nb\n1 2 3\nc\n", b"a\nb\n1 2 4\nc\n").unwrap();
+        assert_eq!(mismatch.line, 3);
+        assert_eq!(mismatch.token, Some(3));
+        assert_eq!(mismatch.expected_token.as_deref(), Some("3"));
+        assert_eq!(mismatch.actual_token.as_deref(), Some("4"));
+        let numbers: Vec<usize> = mismatch.expected_context.iter().map(|x| x.0).collect();
+        assert_eq!(numbers, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn first_mismatch_handles_missing_lines_and_whitespace() {
+        let mismatch = first_mismatch(b"1\n2\n", b"1\n").unwrap();
+        assert_eq!(mismatch.line, 2);
+        assert_eq!(mismatch.expected_token.as_deref(), Some("2"));
+        assert_eq!(mismatch.actual_token, None);
+
+        let mismatch = first_mismatch(b"1 2\n", b"1  2\n").unwrap();
+        assert_eq!(mismatch.line, 1);
+        assert_eq!(mismatch.token, None);
+        let desc = mismatch.to_string();
+        assert!(desc.contains("whitespace"), "desc = {}", desc);
+    }
+
+    #[test]
+    fn first_mismatch_handles_invalid_utf8() {
+        let mismatch = first_mismatch(b"1 \xff\n", b"1 \xfe\n").unwrap();
+        assert_eq!(mismatch.line, 1);
+        assert_eq!(mismatch.token, Some(2));
+
+        let mismatch = first_mismatch(b"1\n\xff\n", b"1\n\xff 2\n").unwrap();
+        assert_eq!(mismatch.line, 2);
+        assert_eq!(mismatch.token, Some(2));
+        assert_eq!(mismatch.expected_token, None);
+    }
+
+    #[test]
+    fn unified_diff_works() {
+        let diff = unified_diff(b"1\n2\n3\n", b"1\n5\n3\n", "expected", "actual");
+        assert!(diff.contains("--- expected"), "diff = {}", diff);
+        assert!(diff.contains("+++ actual"), "diff = {}", diff);
+        assert!(diff.contains("-2\n+5\n"), "diff = {}", diff);
+    }
+}
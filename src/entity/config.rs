@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-use super::gen::GenConfig;
+use std::collections::BTreeMap;
+
+use crate::constants::Constant;
+
+use super::gen::{FormatGenConfig, GenConfig};
 use super::sol::SolutionConfig;
 use super::testcase::TestcaseConfig;
-use super::val::ValidatorConfig;
+use super::val::{OutputValidatorConfig, ValidatorConfig};
 
 /// Config file for creo.
 /// Should be placed at creo.toml
@@ -12,11 +16,26 @@ pub struct CreoConfig {
     /// Time limit in seconds.
     #[serde(default = "time_limit_default")]
     pub time_limit: f64,
+    /// Memory limit in MiB.
+    #[serde(default = "memory_limit_default")]
+    pub memory_limit: u64,
+    /// How many tests should catch each solution whose expected verdict is not AC.
+    #[serde(default = "min_kills_default")]
+    pub min_kills: usize,
+    /// Format of the input files, one string per line, e.g. `["N: int in [1, 2e5]", "A: int[N] in [0, 1e9]"]`.
+    /// Checked by `creo val` in addition to the validators.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub input_format: Vec<String>,
     /// Generators.
     #[serde(default)]
     // Needed by toml: https://github.com/alexcrichton/toml-rs/issues/258.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub generators: Vec<GenConfig>,
+    /// Generators of random inputs from `input_format`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub format_generators: Vec<FormatGenConfig>,
     /// Available languages.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -29,15 +48,51 @@ pub struct CreoConfig {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub validators: Vec<ValidatorConfig>,
+    /// Output validators, which check outputs written by `creo refgen`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub output_validators: Vec<OutputValidatorConfig>,
+    /// Constants shared by the statement, validators, generators and solutions, e.g. `N_MAX = 200000`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub constants: BTreeMap<String, Constant>,
     /// Configuration for input/output files.
     #[serde(default)]
     pub testcase_config: TestcaseConfig,
+    /// Limits of the judge the testset is uploaded to. Checked by `creo stats`.
+    #[serde(default)]
+    pub judge_limits: JudgeLimitsConfig,
 }
 
 fn time_limit_default() -> f64 {
     2.0
 }
 
+fn memory_limit_default() -> u64 {
+    1024
+}
+
+fn min_kills_default() -> usize {
+    1
+}
+
+/// Limits of a judge on a whole testset. Limits that are not given are not checked.
+#[derive(Serialize, Deserialize, Clone, PartialEq, PartialOrd, Debug, Default)]
+pub struct JudgeLimitsConfig {
+    /// Maximum number of testcases.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tests: Option<usize>,
+    /// Maximum total size of the input files in MiB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_total_input_size: Option<f64>,
+    /// Maximum total size of the output files in MiB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_total_output_size: Option<f64>,
+    /// Maximum total time in seconds to judge an AC submission.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_judging_time: Option<f64>,
+}
+
 /// Configuration for an available language.
 #[derive(Serialize, Deserialize, Clone, PartialEq, PartialOrd, Debug)]
 pub struct LanguageConfig {
@@ -50,6 +105,21 @@ pub struct LanguageConfig {
     /// How can we run the compiled binary?
     /// If the given code is a script, this should run the original script.
     pub run: Vec<String>,
+    /// How can we measure line coverage? Used by `creo coverage`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<CoverageConfig>,
+}
+
+/// Configuration for measuring line coverage of a language.
+#[derive(Serialize, Deserialize, Clone, PartialEq, PartialOrd, Debug)]
+pub struct CoverageConfig {
+    /// How can we compile the source code with coverage instrumentation?
+    /// The binary is placed in a directory of its own, where coverage data is also written.
+    pub compile: Vec<String>,
+    /// How can we print the coverage report after all runs?
+    /// This runs in the binary's directory, and should print a report in the text format of `gcov -t`.
+    pub report: Vec<String>,
 }
 
 impl Default for CreoConfig {
@@ -62,6 +132,24 @@ impl Default for CreoConfig {
                 .map(|x| x.to_owned())
                 .collect(),
             run: vec!["$OUT".to_owned()],
+            coverage: Some(CoverageConfig {
+                compile: vec![
+                    "g++",
+                    "--coverage",
+                    "-O0",
+                    "-std=gnu++11",
+                    "-o",
+                    "$OUT",
+                    "$IN",
+                ]
+                .into_iter()
+                .map(|x| x.to_owned())
+                .collect(),
+                report: vec!["sh", "-c", "gcov -t *.gcda"]
+                    .into_iter()
+                    .map(|x| x.to_owned())
+                    .collect(),
+            }),
         };
         let python = LanguageConfig {
             language_name: "Python".to_owned(),
@@ -71,18 +159,28 @@ impl Default for CreoConfig {
                 .map(|x| x.to_owned())
                 .collect(),
             run: vec!["python3".to_owned(), "$OUT".to_owned()],
+            coverage: None,
         };
         let testcase_config = TestcaseConfig {
             indir: "in".to_owned(),
             outdir: "out".to_owned(),
+            invalid_dir: "invalid".to_owned(),
+            samples: "*sample*".to_owned(),
         };
         Self {
             time_limit: 2.0,
+            memory_limit: 1024,
+            min_kills: 1,
+            input_format: vec![],
             generators: vec![],
+            format_generators: vec![],
             languages: vec![cpp, python],
             solutions: vec![],
             validators: vec![],
+            output_validators: vec![],
+            constants: BTreeMap::new(),
             testcase_config,
+            judge_limits: JudgeLimitsConfig::default(),
         }
     }
 }
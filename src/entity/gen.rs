@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Configuration for a generator.
@@ -8,3 +9,52 @@ pub struct GenConfig {
     pub language_name: String,
     pub path: PathBuf,
 }
+
+/// Configuration for generating random inputs from `input_format` in creo.toml.
+#[derive(Serialize, Deserialize, Clone, PartialEq, PartialOrd, Debug)]
+pub struct FormatGenConfig {
+    /// Inputs are named `{name}-{i}.txt` for i = 1, ..., count.
+    pub name: String,
+    /// How many inputs to generate.
+    #[serde(default = "count_default")]
+    pub count: u64,
+    /// How integers outside arrays and repeated lines (such as N) and lengths of strings are chosen.
+    #[serde(default)]
+    pub size: SizePreset,
+    /// The i-th input is generated with seed + i.
+    #[serde(default)]
+    pub seed: u64,
+    /// How values of each variable are drawn, e.g. `{ A = "log" }`. Values are uniform by default.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub distributions: BTreeMap<String, Distribution>,
+}
+
+fn count_default() -> u64 {
+    1
+}
+
+/// How size-like values are chosen.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SizePreset {
+    /// The smallest value allowed.
+    Min,
+    /// The largest value allowed.
+    Max,
+    /// Drawn from the variable's distribution.
+    #[default]
+    Random,
+}
+
+/// How random values are drawn from their range.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Distribution {
+    /// Every value is equally likely.
+    Uniform,
+    /// Small offsets from the minimum are as likely as large ones, in terms of their number of digits.
+    Log,
+    /// Half of the values are the minimum, the maximum or next to them.
+    Edges,
+}
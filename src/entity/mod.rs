@@ -1,5 +1,6 @@
 pub mod config;
 pub mod gen;
+pub mod options;
 pub mod project;
 pub mod sol;
 pub mod testcase;
@@ -0,0 +1,127 @@
+use glob::Pattern;
+use std::path::Path;
+
+/// Command-line options for commands that run solutions or validators on testcases.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct RunOptions {
+    /// If given, only solutions whose path matches this pattern are processed.
+    pub solution: Option<Pattern>,
+    /// If given, only testcases whose name matches this pattern are processed.
+    pub testcase: Option<Pattern>,
+    /// If true, runs whose binary and files are the same as in a recorded run are skipped.
+    pub only_changed: bool,
+    /// If true, a solution stops running once its overall verdict is settled.
+    /// Otherwise every solution runs on every testcase.
+    pub stop_early: bool,
+    /// If true, validators are also run on broken copies of valid inputs, to check that they reject them.
+    pub fuzz: bool,
+    /// If true, inputs that are the same as another input up to whitespace are removed, except the first by name,
+    /// along with their outputs.
+    pub remove_duplicates: bool,
+    /// How many runs may happen at the same time. 0 and 1 both mean one at a time.
+    /// Ignored if `cpus` is not empty.
+    pub jobs: usize,
+    /// If not empty, one run happens on each of these CPUs at the same time,
+    /// and each run is pinned to its CPU.
+    pub cpus: Vec<usize>,
+}
+
+impl RunOptions {
+    /// Should the solution at `path` (as written in creo.toml) be processed?
+    pub fn matches_solution(&self, path: &str) -> bool {
+        self.solution
+            .as_ref()
+            .map_or(true, |pattern| pattern.matches(path))
+    }
+
+    /// Should the testcase `name` (relative to the input directory) be processed?
+    pub fn matches_testcase(&self, name: &Path) -> bool {
+        self.testcase
+            .as_ref()
+            .map_or(true, |pattern| pattern.matches_path(name))
+    }
+
+    /// Are some testcases possibly excluded by a pattern?
+    pub fn is_testcase_filtered(&self) -> bool {
+        self.testcase.is_some()
+    }
+
+    /// How many runs happen at the same time?
+    pub fn workers(&self) -> usize {
+        if self.cpus.is_empty() {
+            std::cmp::max(self.jobs, 1)
+        } else {
+            self.cpus.len()
+        }
+    }
+
+    /// Which CPU should the runs of the `worker`-th worker (in `0..self.workers()`) be pinned to?
+    pub fn cpu_for(&self, worker: usize) -> Option<usize> {
+        self.cpus.get(worker).copied()
+    }
+}
+
+/// The directory `creo stress` and `creo minimize` save inputs in by default, relative to the project directory.
+pub const DEFAULT_COUNTEREXAMPLE_DIR: &str = "counterexamples";
+
+/// Command-line options for `creo stress`.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct StressOptions {
+    /// The generator's path, followed by its arguments. A seed is appended to the arguments on each run.
+    pub generator: Vec<String>,
+    /// Paths of the solutions to compare, as written in creo.toml.
+    /// If only one is given, it is compared with the reference solution.
+    pub solutions: Vec<String>,
+    /// The seed for the first run. Each run uses the next seed of the previous run.
+    pub seed: u64,
+    /// If given, stops after this many runs even if no counterexample is found.
+    pub iterations: Option<u64>,
+    /// The directory counterexamples are saved in, relative to the project directory.
+    pub counterexample_dir: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_options_match_everything() {
+        let options = RunOptions::default();
+        assert!(options.matches_solution("sol/wa.cpp"));
+        assert!(options.matches_testcase(Path::new("1.txt")));
+        assert!(!options.is_testcase_filtered());
+        assert_eq!(options.workers(), 1);
+        assert_eq!(options.cpu_for(0), None);
+    }
+
+    #[test]
+    fn workers_work() {
+        let options = RunOptions {
+            jobs: 4,
+            ..Default::default()
+        };
+        assert_eq!(options.workers(), 4);
+        assert_eq!(options.cpu_for(2), None);
+        let options = RunOptions {
+            jobs: 4,
+            cpus: vec![2, 3],
+            ..Default::default()
+        };
+        assert_eq!(options.workers(), 2);
+        assert_eq!(options.cpu_for(1), Some(3));
+    }
+
+    #[test]
+    fn filters_work() {
+        let options = RunOptions {
+            solution: Some(Pattern::new("*-wa.cpp").unwrap()),
+            testcase: Some(Pattern::new("random-*").unwrap()),
+            ..Default::default()
+        };
+        assert!(options.matches_solution("sol/koba-wa.cpp"));
+        assert!(!options.matches_solution("sol/koba.cpp"));
+        assert!(options.matches_testcase(Path::new("random-01.txt")));
+        assert!(!options.matches_testcase(Path::new("sample-01.txt")));
+        assert!(options.is_testcase_filtered());
+    }
+}
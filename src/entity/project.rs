@@ -1,14 +1,33 @@
+use glob::Pattern;
 use path_clean::PathClean;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsString;
-use std::path::Path;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::entity::config::CreoConfig;
-use crate::entity::sol::Verdict;
+use crate::constants;
+use crate::coverage::parse_gcov;
+use crate::diff::{first_mismatch, unified_diff, Mismatch};
+use crate::entity::config::{CreoConfig, LanguageConfig};
+use crate::entity::options::{RunOptions, StressOptions};
+use crate::entity::sol::{SolutionConfig, Verdict};
 use crate::entity::testcase::TestcaseConfig;
-use crate::entity::val::ValidatorConfig;
+use crate::entity::val::{Bound, ValidatorConfig};
 use crate::error::{Error, Result};
+use crate::format::InputFormat;
+use crate::fuzz::{malformed_inputs, out_of_bounds_inputs};
 use crate::io_util::{IoUtil, IoUtilExt};
-use crate::run_util::{RunUtil, RunUtilExt};
+use crate::lint::{duplicate_groups, format_problems};
+use crate::minimize::{candidates, is_smaller};
+use crate::mutate::mutants;
+use crate::par_util::run_in_order;
+use crate::run_util::{sha256_hex, Limits, RunResult, RunUtil, RunUtilExt, CACHE_DIR};
+use crate::statement::{
+    self, outline, sample_blocks, translation_differences, Sample, SampleBlock, SampleKind,
+};
+use crate::stats::{format_size, judge_limit_warnings, TestsetStats};
+use crate::testlib::{parse_overview_log, BoundsHit};
 
 /// A trait that provides functions to handle a project directory.
 pub trait Project {
@@ -29,17 +48,89 @@ pub trait Project {
     }
     /// Generate output files from input files and a reference solution.
     #[allow(unused)]
-    fn refgen(&mut self, proj_dir: &str) -> Result<()> {
+    fn refgen(&mut self, proj_dir: &str, options: &RunOptions) -> Result<()> {
         unreachable!();
     }
     /// Execute all solutions and check if their output matches expected output.
     #[allow(unused)]
-    fn test(&mut self, proj_dir: &str) -> Result<()> {
+    fn test(&mut self, proj_dir: &str, options: &RunOptions) -> Result<()> {
         unreachable!();
     }
     /// Validate all input files.
     #[allow(unused)]
-    fn val(&mut self, proj_dir: &str) -> Result<()> {
+    fn val(&mut self, proj_dir: &str, options: &RunOptions) -> Result<()> {
+        unreachable!();
+    }
+    /// Run a solution on a testcase and print a unified diff between the expected and actual output.
+    #[allow(unused)]
+    fn show_diff(&mut self, proj_dir: &str, solution: &str, testcase: &str) -> Result<()> {
+        unreachable!();
+    }
+    /// Run all solutions expected to be AC on all testcases and report testcases on which their outputs differ.
+    #[allow(unused)]
+    fn cross_check(&mut self, proj_dir: &str, options: &RunOptions) -> Result<()> {
+        unreachable!();
+    }
+    /// Run a solution on a testcase, a file or the standard input under the time and memory limits.
+    #[allow(unused)]
+    fn run_solution(&mut self, proj_dir: &str, solution: &str, input: Option<&str>) -> Result<()> {
+        unreachable!();
+    }
+    /// Compare two solutions on random inputs until their outputs differ.
+    #[allow(unused)]
+    fn stress(&mut self, proj_dir: &str, options: &StressOptions) -> Result<()> {
+        unreachable!();
+    }
+    /// Shrink an input on which a solution gets a wrong verdict, keeping the input valid.
+    /// The result is saved in counterexample_dir, relative to the project directory.
+    #[allow(unused)]
+    fn minimize(
+        &mut self,
+        proj_dir: &str,
+        solution: &str,
+        testcase: &str,
+        counterexample_dir: &str,
+    ) -> Result<()> {
+        unreachable!();
+    }
+    /// Run mutants of the reference solution on all testcases and report the mutants no testcase catches.
+    #[allow(unused)]
+    fn mutate(
+        &mut self,
+        proj_dir: &str,
+        max_mutants: Option<usize>,
+        options: &RunOptions,
+    ) -> Result<()> {
+        unreachable!();
+    }
+    /// Run the reference solution built for coverage on all testcases and report lines that never ran.
+    #[allow(unused)]
+    fn coverage(&mut self, proj_dir: &str) -> Result<()> {
+        unreachable!();
+    }
+    /// Print the number, sizes and recorded judging times of the testcases.
+    #[allow(unused)]
+    fn stats(&mut self, proj_dir: &str) -> Result<()> {
+        unreachable!();
+    }
+    /// Render statements into HTML.
+    #[allow(unused)]
+    fn statement(&mut self, proj_dir: &str) -> Result<()> {
+        unreachable!();
+    }
+    /// Check samples in statements against the testset and the reference solution.
+    #[allow(unused)]
+    fn check_samples(&mut self, proj_dir: &str) -> Result<()> {
+        unreachable!();
+    }
+    /// Check that translations of the statement are consistent with each other.
+    #[allow(unused)]
+    fn check_translations(&mut self, proj_dir: &str, accept: bool) -> Result<()> {
+        unreachable!();
+    }
+    /// Write the constants as source files and substitute them into statements.
+    #[allow(unused)]
+    fn constants(&mut self, proj_dir: &str, statement: Option<&str>) -> Result<()> {
         unreachable!();
     }
 }
@@ -64,27 +155,350 @@ pub trait ProjectExt: IoUtil + RunUtil {
         Ok(())
     }
 
-    // Given the result of execution and the expected output file, find the verdict.
-    fn get_verdict(&mut self, result: &Result<Vec<u8>>, outfile: &Path) -> Result<Verdict> {
-        match result {
-            Ok(result) => {
-                let mut file = self.open_file_for_read(outfile)?;
-                let content = self.read_bytes_from_file(&mut file)?;
-                if &content == result {
-                    Ok(Verdict::AC)
-                } else {
-                    Ok(Verdict::WA)
+    // Writes the constants in the config as source files next to every generator, validator and solution,
+    // so that they can be included before compiling. Files whose content is unchanged are not rewritten,
+    // and files that creo did not generate are never overwritten.
+    fn write_constants(&mut self, proj: &Path, config: &CreoConfig) -> Result<()> {
+        if config.constants.is_empty() {
+            return Ok(());
+        }
+        constants::check_names(&config.constants)
+            .map_err(|description| Error::ConfInvalid { description })?;
+        let sources = constants::sources(&config.constants);
+        let paths = config
+            .generators
+            .iter()
+            .map(|gen| gen.path.as_path())
+            .chain(config.validators.iter().map(|val| Path::new(&val.path)))
+            .chain(
+                config
+                    .output_validators
+                    .iter()
+                    .map(|val| Path::new(&val.path)),
+            )
+            .chain(config.solutions.iter().map(|sol| Path::new(&sol.path)));
+        let dirs: BTreeSet<PathBuf> = paths
+            .map(|path| proj.join(path).join("..").clean())
+            .collect();
+        for dir in dirs {
+            for (name, content) in &sources {
+                let path = dir.join(name);
+                match self.read_file_if_exists(&path)? {
+                    Some(old) if old == content.as_bytes() => continue,
+                    Some(old) if !constants::is_generated(&old) => {
+                        return Err(Error::NotGeneratedByCreo {
+                            path: path.display().to_string(),
+                        });
+                    }
+                    _ => self.overwrite_file(&path, content.as_bytes())?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Collects the sizes and the recorded times of the reference solution of all testcases,
+    // in total and by the generator that made them.
+    fn testset_stats(
+        &mut self,
+        proj_dir: &Path,
+        config: &CreoConfig,
+    ) -> Result<(TestsetStats, BTreeMap<String, TestsetStats>)> {
+        let TestcaseConfig { indir, outdir, .. } = &config.testcase_config;
+        let indir = proj_dir.join(indir);
+        let outdir = proj_dir.join(outdir);
+        let mut testcases = self.list_dir(&indir)?;
+        testcases.sort();
+
+        // Recorded times of the reference solution are taken as the time to judge an AC submission.
+        let reference = config
+            .solutions
+            .iter()
+            .find(|sol| sol.is_reference_solution)
+            .map(|sol| proj_dir.join(&sol.path));
+
+        let mut total = TestsetStats::default();
+        let mut by_generator: BTreeMap<String, TestsetStats> = BTreeMap::new();
+        for testcase in &testcases {
+            let name = testcase.display().to_string();
+            let infile = indir.join(testcase);
+            let input_size = self.file_size_if_exists(&infile)?.unwrap_or_default();
+            let output_size = self
+                .file_size_if_exists(&outdir.join(testcase))?
+                .unwrap_or_default();
+            let mut time = None;
+            if let Some(src) = &reference {
+                let key = self.time_key(src, &infile)?;
+                time = self.read_record(&key)?.and_then(|time| time.parse().ok());
+            }
+            let generator = self
+                .read_record(&self.origin_key(&indir, testcase)?)?
+                .unwrap_or_else(|| "(unknown)".to_owned());
+            for stats in [&mut total, by_generator.entry(generator).or_default()] {
+                stats.add(&name, input_size, output_size, time);
+            }
+        }
+        Ok((total, by_generator))
+    }
+
+    // Reads a statement and replaces every {{NAME}} in it with the value of the constant NAME.
+    fn substitute_constants(&mut self, path: &Path, config: &CreoConfig) -> Result<String> {
+        let mut file = self.open_file_for_read(path)?;
+        let text = self.read_from_file(&mut file)?;
+        constants::substitute(&text, &config.constants).map_err(|names| Error::UndefinedConstants {
+            file: path.display().to_string(),
+            names: names.join(", "),
+        })
+    }
+
+    // Finds the names of the testcases that match the sample pattern, in order of name.
+    fn sample_testcases(&mut self, proj_dir: &Path, config: &CreoConfig) -> Result<Vec<PathBuf>> {
+        let TestcaseConfig { indir, samples, .. } = &config.testcase_config;
+        let pattern = Pattern::new(samples).map_err(|e| Error::ConfInvalid {
+            description: format!("samples = {:?} is not a valid pattern: {}", samples, e),
+        })?;
+        let mut testcases = self.list_dir(&proj_dir.join(indir))?;
+        testcases.retain(|testcase| pattern.matches_path(testcase));
+        testcases.sort();
+        Ok(testcases)
+    }
+
+    // Reads the testcases that match the sample pattern, in order of name.
+    fn read_samples(&mut self, proj_dir: &Path, config: &CreoConfig) -> Result<Vec<Sample>> {
+        let TestcaseConfig { indir, outdir, .. } = &config.testcase_config;
+        let indir = proj_dir.join(indir);
+        let outdir = proj_dir.join(outdir);
+        let mut samples = vec![];
+        for testcase in self.sample_testcases(proj_dir, config)? {
+            let mut file = self.open_file_for_read(&indir.join(&testcase))?;
+            let input = self.read_bytes_from_file(&mut file)?;
+            let input = String::from_utf8_lossy(&input).into_owned();
+            let Some(output) = self.read_file_if_exists(&outdir.join(&testcase))? else {
+                return Err(Error::SampleOutputMissing {
+                    testcase: testcase.display().to_string(),
+                });
+            };
+            let output = String::from_utf8_lossy(&output).into_owned();
+            samples.push(Sample { input, output });
+        }
+        Ok(samples)
+    }
+
+    // Compiles a source file in the project.
+    // Returns the directory the binary should run in and the path to the binary.
+    fn compile_in_project(
+        &mut self,
+        proj_dir: &Path,
+        path: &Path,
+        lang_config: &LanguageConfig,
+    ) -> Result<(PathBuf, PathBuf)> {
+        let src = proj_dir.join(path);
+        let cd = src.join("..").clean();
+        let cd = self.to_absolute(&cd)?;
+        let outpath = self.compile(&cd, &self.to_absolute(&src)?, &lang_config.compile)?;
+        Ok((cd, outpath))
+    }
+
+    // Saves content to a file in the cache directory and returns its path.
+    // The file name is derived from the content, so the same content is saved only once.
+    fn save_to_cache(&mut self, kind: &str, content: &[u8]) -> Result<PathBuf> {
+        let path = Path::new(CACHE_DIR).join(kind).join(sha256_hex(&[content]));
+        if !path.is_file() {
+            let mut file = self.create_file_if_nonexistent(&path, 0o644)?;
+            self.write_bytes_to_file(&mut file, content)?;
+        }
+        Ok(path)
+    }
+
+    // Computes the key under which the result of a run is recorded.
+    // The key depends on the binary, how it is run, anything else in `extra` that affects the result,
+    // and the contents of the given files.
+    fn record_key(
+        &self,
+        kind: &str,
+        exec: &Path,
+        run: &[String],
+        extra: &[&str],
+        files: &[&Path],
+    ) -> Result<String> {
+        let mut contents = vec![];
+        for &path in files {
+            let mut file = self.open_file_for_read(path)?;
+            contents.push(self.read_bytes_from_file(&mut file)?);
+        }
+        let exec = exec.display().to_string();
+        // The number of run arguments separates them from `extra`.
+        let run_len = run.len().to_string();
+        // Each chunk is prefixed with its length so that different chunks never collide.
+        let mut key = vec![];
+        let chunks = [kind.as_bytes(), exec.as_bytes(), run_len.as_bytes()]
+            .into_iter()
+            .chain(run.iter().map(|x| x.as_bytes()))
+            .chain(extra.iter().map(|x| x.as_bytes()))
+            .chain(contents.iter().map(|x| x.as_slice()));
+        for chunk in chunks {
+            key.extend_from_slice(format!("{}:", chunk.len()).as_bytes());
+            key.extend_from_slice(chunk);
+        }
+        Ok(sha256_hex(&[&key]))
+    }
+
+    // Reads the result recorded under key, if any.
+    fn read_record(&self, key: &str) -> Result<Option<String>> {
+        let path = Path::new(CACHE_DIR).join("results").join(key);
+        match self.open_file_for_read(&path) {
+            Ok(mut file) => Ok(Some(self.read_from_file(&mut file)?)),
+            Err(Error::IOError(e)) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Records the result of a run under key, overwriting the previous one.
+    fn write_record(&self, key: &str, value: &str) -> Result<()> {
+        let path = Path::new(CACHE_DIR).join("results").join(key);
+        self.overwrite_file(&path, value.as_bytes())
+    }
+
+    // Computes the key under which the generator that made the input `testcase` in `indir` is recorded.
+    // The key depends on the directory and the name as well as the content,
+    // so that identical inputs in different places have their own origins.
+    fn origin_key(&self, indir: &Path, testcase: &Path) -> Result<String> {
+        let name = testcase.display().to_string();
+        let infile = indir.join(testcase);
+        self.record_key(
+            "origin",
+            &self.to_absolute(indir)?,
+            &[],
+            &[&name],
+            &[&infile],
+        )
+    }
+
+    // Computes the key under which the time a solution took on an input is recorded.
+    // The key depends on the source rather than the binary, so that it can be found without compiling.
+    fn time_key(&self, src: &Path, infile: &Path) -> Result<String> {
+        self.record_key("time", Path::new(""), &[], &[], &[src, infile])
+    }
+
+    // Reads a file, or returns None if the file doesn't exist.
+    fn read_file_if_exists(&self, path: &Path) -> Result<Option<Vec<u8>>> {
+        match self.open_file_for_read(path) {
+            Ok(mut file) => Ok(Some(self.read_bytes_from_file(&mut file)?)),
+            Err(Error::IOError(e)) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Gets the size of a file, or returns None if the file doesn't exist.
+    fn file_size_if_exists(&self, path: &Path) -> Result<Option<u64>> {
+        match self.file_size(path) {
+            Ok(size) => Ok(Some(size)),
+            Err(Error::IOError(e)) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Lists files in a directory, or returns an empty list if the directory doesn't exist.
+    fn list_dir_if_exists(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        match self.list_dir(path) {
+            Err(Error::IOError(e)) if e.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
+            result => result,
+        }
+    }
+
+    // Computes the hash of a file's content, or returns None if the file doesn't exist.
+    fn hash_file_if_exists(&self, path: &Path) -> Result<Option<String>> {
+        Ok(self
+            .read_file_if_exists(path)?
+            .map(|content| sha256_hex(&[&content])))
+    }
+
+    // Runs a validator on an input file.
+    // Returns Error::ValidationFailed if the validator rejects the input.
+    fn validate(
+        &mut self,
+        validator: &str,
+        cd: &Path,
+        exec: &Path,
+        run: &[String],
+        infile: &Path,
+    ) -> Result<()> {
+        if let Err(e) = self.run_with_input(cd, exec, run, infile) {
+            // A hack to check if the subprocess exited with status code != 0.
+            if let Error::IOError(ref inner) = e {
+                if inner.kind() == std::io::ErrorKind::InvalidData {
+                    let inner = match e {
+                        Error::IOError(inner) => inner,
+                        _ => unreachable!(),
+                    };
+
+                    return Err(Error::ValidationFailed {
+                        validator: validator.to_owned(),
+                        infile: infile.display().to_string(),
+                        inner: Box::new(inner) as Box<dyn std::error::Error + Send + Sync>,
+                    });
                 }
             }
-            Err(_e) => {
-                // TODO: We need to inspect what kind of error happened.
-                Ok(Verdict::RE)
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    // Finds the verdict of a solution on an input, using the output of the reference solution as the expected output.
+    // Returns None if the input is rejected by a validator or the reference solution fails on it.
+    fn verdict_on_input(
+        &mut self,
+        validators: &[(&str, &LanguageConfig, PathBuf, PathBuf)],
+        reference: &(&str, &LanguageConfig, PathBuf, PathBuf),
+        solution: &(&str, &LanguageConfig, PathBuf, PathBuf),
+        limits: &Limits,
+        input: &[u8],
+    ) -> Result<Option<Verdict>> {
+        let infile = self.save_to_cache("minimize", input)?;
+        for (path, lang_config, cd, outpath) in validators {
+            match self.validate(path, cd, outpath, &lang_config.run, &infile) {
+                Err(Error::ValidationFailed { .. }) => return Ok(None),
+                result => result?,
             }
         }
+        let (_, lang_config, cd, outpath) = solution;
+        let result = self.run_with_limits(cd, outpath, &lang_config.run, &infile, limits)?;
+        if let Some(verdict) = failure_verdict(&result, limits) {
+            return Ok(Some(verdict));
+        }
+        let (_, ref_lang_config, ref_cd, ref_outpath) = reference;
+        let expected =
+            self.run_with_limits(ref_cd, ref_outpath, &ref_lang_config.run, &infile, limits)?;
+        if failure_verdict(&expected, limits).is_some() {
+            return Ok(None);
+        }
+        match first_mismatch(&expected.stdout, &result.stdout) {
+            None => Ok(Some(Verdict::AC)),
+            Some(_) => Ok(Some(Verdict::WA)),
+        }
+    }
+
+    // Given the result of execution and the expected output file, find the verdict.
+    // On WA, the first mismatch between the expected and actual output is returned as well.
+    fn get_verdict(
+        &self,
+        result: &RunResult,
+        limits: &Limits,
+        outfile: &Path,
+    ) -> Result<(Verdict, Option<Mismatch>)> {
+        if let Some(verdict) = failure_verdict(result, limits) {
+            return Ok((verdict, None));
+        }
+        let mut file = self.open_file_for_read(outfile)?;
+        let content = self.read_bytes_from_file(&mut file)?;
+        match first_mismatch(&content, &result.stdout) {
+            None => Ok((Verdict::AC, None)),
+            Some(mismatch) => Ok((Verdict::WA, Some(mismatch))),
+        }
     }
 }
 
-impl<T: ProjectExt> Project for T {
+impl<T: ProjectExt + Sync> Project for T {
     fn add(&mut self, proj_dir: &str, ty: &str, name: &str) -> Result<()> {
         let proj = Path::new(proj_dir);
 
@@ -136,6 +550,8 @@ done
             config.validators.push(ValidatorConfig {
                 path: name.into_os_string().into_string().unwrap(),
                 language_name: lang,
+                overview_log: false,
+                required_bounds: Default::default(),
             });
             self.write_config(proj, &config)?;
             return Ok(());
@@ -165,6 +581,7 @@ done
 
         // Read the config file
         let config = self.read_config(proj)?;
+        self.write_constants(proj, &config)?;
         let lang_configs = config.languages;
         let TestcaseConfig { indir, .. } = config.testcase_config;
         let indir = proj.join(indir);
@@ -173,33 +590,57 @@ done
         self.remove_dir_all(&indir)?;
         self.mkdir_p(&indir)?;
 
+        // Inputs made by earlier generators
+        let mut generated = BTreeSet::new();
         for gen in config.generators {
-            let src = proj.join(&gen.path);
-            let cd = src.join("..").clean();
-            let cd = self.to_absolute(&cd)?;
-            let lang_config = lang_configs
-                .iter()
-                .find(|&c| c.language_name == gen.language_name);
-            if let Some(x) = lang_config {
-                let outpath = self.compile(&cd, &self.to_absolute(&src)?, &x.compile)?;
-                self.run_once(&cd, &outpath, &x.run)?;
-            } else {
-                eprintln!("warning");
-                let e = Error::ConfInvalid {
-                    description: format!("language not found: {}", gen.language_name),
-                };
-                return Err(e);
+            let lang_config = find_language(&lang_configs, &gen.language_name)?;
+            let (cd, outpath) = self.compile_in_project(proj, &gen.path, lang_config)?;
+            self.run_once(&cd, &outpath, &lang_config.run)?;
+            // Record which generator made each new input, for `creo stats`.
+            for infile in self.list_dir(&indir)? {
+                if generated.insert(infile.clone()) {
+                    let key = self.origin_key(&indir, &infile)?;
+                    self.write_record(&key, &gen.path.display().to_string())?;
+                }
+            }
+        }
+
+        // Generate random inputs from the input format.
+        if config.format_generators.is_empty() {
+            return Ok(());
+        }
+        if config.input_format.is_empty() {
+            return Err(Error::ConfInvalid {
+                description: "format_generators need input_format in creo.toml".to_owned(),
+            });
+        }
+        let input_format = InputFormat::parse(&config.input_format)
+            .map_err(|description| Error::ConfInvalid { description })?;
+        for gen in &config.format_generators {
+            let origin = format!("{} ({})", INPUT_FORMAT, gen.name);
+            for i in 1..=gen.count {
+                let input = input_format
+                    .generate(gen.size, &gen.distributions, gen.seed.wrapping_add(i))
+                    .map_err(|e| Error::ConfInvalid {
+                        description: format!("Generating {} failed: {}", gen.name, e),
+                    })?;
+                let testcase = PathBuf::from(format!("{}-{}.txt", gen.name, i));
+                self.overwrite_file(&indir.join(&testcase), input.as_bytes())?;
+                self.write_record(&self.origin_key(&indir, &testcase)?, &origin)?;
             }
+            eprintln!("Generated {} inputs from {}", gen.count, origin);
         }
         Ok(())
     }
-    fn refgen(&mut self, proj_dir: &str) -> Result<()> {
+    fn refgen(&mut self, proj_dir: &str, options: &RunOptions) -> Result<()> {
         let proj_dir = Path::new(proj_dir);
 
         // Read the config file
         let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let limits = limits(&config);
         let lang_configs = config.languages;
-        let TestcaseConfig { indir, outdir } = config.testcase_config;
+        let TestcaseConfig { indir, outdir, .. } = config.testcase_config;
         let indir = proj_dir.join(indir);
         let outdir = proj_dir.join(outdir);
 
@@ -219,158 +660,1822 @@ done
             return Err(e);
         }
 
-        // Delete all files in outdir
-        self.remove_dir_all(&outdir)?;
+        let sol = config
+            .solutions
+            .iter()
+            .find(|&solution| solution.is_reference_solution)
+            .unwrap();
+        // Only the reference solution makes outputs, so a filter that excludes it would do nothing.
+        if !options.matches_solution(&sol.path) {
+            let pattern = options.solution.as_ref().unwrap();
+            return Err(Error::ReferenceSolutionFiltered {
+                pattern: pattern.as_str().to_owned(),
+                reference: sol.path.clone(),
+            });
+        }
+
+        // Delete all files in outdir, unless only some of them are generated
+        let partial = options.is_testcase_filtered() || options.only_changed;
+        if !partial {
+            self.remove_dir_all(&outdir)?;
+        }
 
-        for sol in config.solutions {
-            if !sol.is_reference_solution {
-                continue;
-            }
-            let src = proj_dir.join(&sol.path);
-            let cd = src.join("..").clean();
-            let cd = self.to_absolute(&cd)?;
-            let lang_config = lang_configs
-                .iter()
-                .find(|&c| c.language_name == sol.language_name);
-            if let Some(x) = lang_config {
-                let outpath = self.compile(&cd, &self.to_absolute(&src)?, &x.compile)?;
-                // For all files in `indir`, generate the counterpart in `outdir`.
-                for infile in self.list_dir(&indir)? {
-                    eprintln!("Generating {}", infile.to_str().unwrap());
-                    let outfile = outdir.join(&infile);
-                    let infile = indir.join(&infile);
-                    self.run_pipe(&cd, &outpath, &x.run, &infile, &outfile)?;
+        let lang_config = find_language(&lang_configs, &sol.language_name)?;
+        let (cd, outpath) = self.compile_in_project(proj_dir, Path::new(&sol.path), lang_config)?;
+        let mut output_validators = vec![];
+        for val in &config.output_validators {
+            let lang_config = find_language(&lang_configs, &val.language_name)?;
+            let (cd, outpath) =
+                self.compile_in_project(proj_dir, Path::new(&val.path), lang_config)?;
+            output_validators.push((val.path.as_str(), lang_config, cd, outpath));
+        }
+        let mut testcases = self.list_dir(&indir)?;
+        testcases.retain(|infile| options.matches_testcase(infile));
+
+        // For all files in `indir`, generate the counterpart in `outdir`.
+        // Once a run fails, the remaining runs are skipped.
+        let mut error = None;
+        let mut written = vec![];
+        let failed = AtomicBool::new(false);
+        let this = &*self;
+        run_in_order(
+            &testcases,
+            options.workers(),
+            |worker, _, infile| -> Result<Option<RunResult>> {
+                if failed.load(Ordering::SeqCst) {
+                    return Ok(None);
                 }
-            } else {
-                eprintln!("warning");
-                let e = Error::ConfInvalid {
-                    description: format!("language not found: {}", sol.language_name),
+                let outfile = outdir.join(infile);
+                let infile = indir.join(infile);
+                let key = this.record_key("refgen", &outpath, &lang_config.run, &[], &[&infile])?;
+                if options.only_changed {
+                    let output_hash = this.hash_file_if_exists(&outfile)?;
+                    if output_hash.is_some() && this.read_record(&key)? == output_hash {
+                        return Ok(None);
+                    }
+                }
+                let limits = Limits {
+                    cpu: options.cpu_for(worker),
+                    ..limits.clone()
                 };
-                return Err(e);
+                let result =
+                    this.run_with_limits(&cd, &outpath, &lang_config.run, &infile, &limits)?;
+                let time_key = this.time_key(&proj_dir.join(&sol.path), &infile)?;
+                this.write_record(&time_key, &result.elapsed.as_secs_f64().to_string())?;
+                if failure_verdict(&result, &limits).is_none() {
+                    this.overwrite_file(&outfile, &result.stdout)?;
+                    this.write_record(&key, &sha256_hex(&[&result.stdout]))?;
+                }
+                Ok(Some(result))
+            },
+            |index, outcome| {
+                if error.is_some() {
+                    return;
+                }
+                let name = testcases[index].display().to_string();
+                match outcome {
+                    Ok(None) => eprintln!("Skipping {} (unchanged)", name),
+                    Ok(Some(result)) => match failure_verdict(&result, &limits) {
+                        None => {
+                            eprintln!("Generating {}", name);
+                            written.push(&testcases[index]);
+                        }
+                        Some(verdict) => {
+                            eprintln!("Generating {} failed: {:?}", name, verdict);
+                            error = Some(Error::RunFailed {
+                                path: sol.path.clone(),
+                                infile: indir.join(&testcases[index]).display().to_string(),
+                                verdict,
+                            });
+                        }
+                    },
+                    Err(e) => error = Some(e),
+                }
+                if error.is_some() {
+                    failed.store(true, Ordering::SeqCst);
+                }
+            },
+        );
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        // Check each written output with all output validators.
+        // A malformed output is removed, so that it is generated again even with --only-changed.
+        for testcase in written {
+            let infile = self.to_absolute(&indir.join(testcase))?;
+            let outfile = outdir.join(testcase);
+            for (path, lang_config, cd, outpath) in &output_validators {
+                eprintln!("Validating output {}", testcase.display());
+                let mut run = lang_config.run.clone();
+                run.push(infile.display().to_string());
+                if let Err(e) = self.validate(path, cd, outpath, &run, &outfile) {
+                    self.remove_file(&outfile)?;
+                    return Err(e);
+                }
             }
         }
         Ok(())
     }
-    fn test(&mut self, proj_dir: &str) -> Result<()> {
+    fn test(&mut self, proj_dir: &str, options: &RunOptions) -> Result<()> {
         let proj_dir = Path::new(proj_dir);
 
         // Read the config file
         let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let limits = limits(&config);
         let lang_configs = config.languages;
-        let TestcaseConfig { indir, outdir } = config.testcase_config;
+        let TestcaseConfig { indir, outdir, .. } = config.testcase_config;
         let indir = proj_dir.join(indir);
         let outdir = proj_dir.join(outdir);
 
-        for sol in config.solutions {
-            let src = proj_dir.join(&sol.path);
-            let cd = src.join("..").clean();
-            let cd = self.to_absolute(&cd)?;
-            let lang_config = lang_configs
+        // Compile all solutions to test
+        let mut targets = vec![];
+        for sol in &config.solutions {
+            if !options.matches_solution(&sol.path) {
+                continue;
+            }
+            let lang_config = find_language(&lang_configs, &sol.language_name)?;
+            let (cd, outpath) =
+                self.compile_in_project(proj_dir, Path::new(&sol.path), lang_config)?;
+            targets.push((sol, lang_config, cd, outpath));
+        }
+        // Verdicts depend on the limits, so they are part of the keys of recorded verdicts.
+        let limits_key = limits.to_key();
+        let mut testcases = self.list_dir(&indir)?;
+        testcases.retain(|infile| options.matches_testcase(infile));
+
+        // Runs of timing-sensitive solutions are not run in parallel with other runs,
+        // unless each run has a dedicated CPU.
+        let (exclusive, shared): (Vec<_>, Vec<_>) = (0..targets.len())
+            .flat_map(|target| testcases.iter().map(move |infile| (target, infile)))
+            .partition(|&(target, _)| {
+                targets[target].0.timing_sensitive && options.cpus.is_empty()
+            });
+
+        let mut overall_verdicts = vec![Verdict::AC; targets.len()];
+        // Testcases on which each solution doesn't get AC
+        let mut kills: Vec<Vec<&Path>> = vec![vec![]; targets.len()];
+        let mut remaining = vec![testcases.len(); targets.len()];
+        let settled: Vec<AtomicBool> = targets.iter().map(|_| AtomicBool::new(false)).collect();
+        let mut error = None;
+        if testcases.is_empty() {
+            for &(sol, ..) in &targets {
+                report_test_result(proj_dir, sol, &Verdict::AC, options, &mut error);
+            }
+        }
+        let this = &*self;
+        for (tasks, workers) in [(shared, options.workers()), (exclusive, 1)] {
+            run_in_order(
+                &tasks,
+                workers,
+                |worker, _, &(target, infile)| -> Result<TestOutcome> {
+                    let (sol, lang_config, cd, outpath) = &targets[target];
+                    // For all files in `indir`, compare the output with the counterpart in `outdir`.
+                    if settled[target].load(Ordering::SeqCst) {
+                        return Ok(TestOutcome::Skipped);
+                    }
+                    let outfile = outdir.join(infile);
+                    let infile = indir.join(infile);
+                    let key = this.record_key(
+                        "test",
+                        outpath,
+                        &lang_config.run,
+                        &[&limits_key],
+                        &[&infile, &outfile],
+                    )?;
+                    if options.only_changed {
+                        if let Some(verdict) = this.read_record(&key)? {
+                            return Ok(TestOutcome::Recorded(toml::from_str(&verdict)?));
+                        }
+                    }
+                    let limits = Limits {
+                        cpu: options.cpu_for(worker),
+                        ..limits.clone()
+                    };
+                    let result =
+                        this.run_with_limits(cd, outpath, &lang_config.run, &infile, &limits)?;
+                    let time_key = this.time_key(&proj_dir.join(&sol.path), &infile)?;
+                    this.write_record(&time_key, &result.elapsed.as_secs_f64().to_string())?;
+                    let (verdict, mismatch) = this.get_verdict(&result, &limits, &outfile)?;
+                    this.write_record(&key, &toml::to_string(&verdict)?)?;
+                    Ok(TestOutcome::Ran(verdict, mismatch))
+                },
+                |index, outcome| {
+                    let (target, infile) = tasks[index];
+                    let sol = targets[target].0;
+                    let overall_verdict = &mut overall_verdicts[target];
+                    remaining[target] -= 1;
+                    // Results after the verdict is settled are ignored, even if they are available.
+                    if !settled[target].load(Ordering::SeqCst) {
+                        let name = infile.display();
+                        match outcome {
+                            Ok(TestOutcome::Skipped) => {}
+                            Ok(TestOutcome::Recorded(verdict)) => {
+                                if verdict != Verdict::AC {
+                                    kills[target].push(infile);
+                                }
+                                *overall_verdict =
+                                    std::cmp::max(overall_verdict.clone(), verdict.clone());
+                                eprintln!(
+                                    "Skipping {} (unchanged, recorded: {:?}) (overall: {:?})",
+                                    name, verdict, overall_verdict
+                                );
+                            }
+                            Ok(TestOutcome::Ran(verdict, mismatch)) => {
+                                if verdict != Verdict::AC {
+                                    kills[target].push(infile);
+                                }
+                                *overall_verdict =
+                                    std::cmp::max(overall_verdict.clone(), verdict.clone());
+                                eprintln!(
+                                    "Running {} {:?} (overall: {:?})",
+                                    name, verdict, overall_verdict
+                                );
+                                if let Some(mismatch) = mismatch {
+                                    eprint!("{}", mismatch);
+                                }
+                            }
+                            Err(e) => {
+                                eprintln!("Running {} failed: {}", name, e);
+                                error.get_or_insert(e);
+                            }
+                        }
+                        if options.stop_early
+                            && is_verdict_settled(&sol.expected_verdict, overall_verdict)
+                        {
+                            settled[target].store(true, Ordering::SeqCst);
+                            if remaining[target] > 0 {
+                                eprintln!(
+                                    "Stopping early: the overall verdict {:?} is settled (expected: {:?})",
+                                    overall_verdict, sol.expected_verdict
+                                );
+                            }
+                        }
+                    }
+                    if remaining[target] == 0 {
+                        report_test_result(proj_dir, sol, overall_verdict, options, &mut error);
+                    }
+                },
+            );
+        }
+
+        // Kills are known only if every solution ran on every testcase.
+        if !options.stop_early && !options.is_testcase_filtered() {
+            let wrong_solutions: Vec<(&SolutionConfig, &[&Path])> = targets
                 .iter()
-                .find(|&c| c.language_name == sol.language_name);
-            if let Some(x) = lang_config {
-                let outpath = self.compile(&cd, &self.to_absolute(&src)?, &x.compile)?;
-                // For all files in `indir`, generate the counterpart in `outdir`.
-                let mut overall_verdict = Verdict::AC;
-                for infile in self.list_dir(&indir)? {
-                    eprint!("Running {}", infile.to_str().unwrap());
-                    let outfile = outdir.join(&infile);
-                    let infile = indir.join(&infile);
-
-                    let result = self.run_with_input(&cd, &outpath, &x.run, &infile);
-                    let verdict = self.get_verdict(&result, &outfile)?;
-                    overall_verdict = std::cmp::max(overall_verdict, verdict.clone());
-                    eprintln!(" {:?} (overall: {:?})", verdict, overall_verdict);
-                }
-                if sol.expected_verdict != overall_verdict {
-                    return Err(Error::VerdictMismatch {
-                        expected: sol.expected_verdict,
-                        actual: overall_verdict,
-                    });
-                } else {
+                .zip(&kills)
+                .filter(|((sol, ..), _)| sol.expected_verdict != Verdict::AC)
+                .map(|((sol, ..), kills)| (*sol, kills.as_slice()))
+                .collect();
+            report_kills(
+                &wrong_solutions,
+                &testcases,
+                config.min_kills,
+                options.solution.is_none(),
+                &mut error,
+            );
+        }
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    fn val(&mut self, proj_dir: &str, options: &RunOptions) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let lang_configs = config.languages;
+        let TestcaseConfig {
+            indir,
+            outdir,
+            invalid_dir,
+            ..
+        } = config.testcase_config;
+        let indir = proj_dir.join(indir);
+        let outdir = proj_dir.join(outdir);
+        let invalid_dir = proj_dir.join(invalid_dir);
+
+        // Check the format of each file in `indir` and `outdir`, which needs no validator.
+        // `outdir` may not exist before refgen.
+        let mut format_problem_count = 0;
+        // Names and contents of input files, sorted by name
+        let mut inputs = vec![];
+        for dir in [&indir, &outdir] {
+            if dir == &outdir && !outdir.is_dir() {
+                continue;
+            }
+            for testcase in self.list_dir(dir)? {
+                if !options.matches_testcase(&testcase) {
+                    continue;
+                }
+                let path = dir.join(&testcase);
+                let mut file = self.open_file_for_read(&path)?;
+                let content = self.read_bytes_from_file(&mut file)?;
+                for problem in format_problems(&content) {
                     eprintln!(
-                        "Testing {} complete (result = expected = {:?})",
-                        src.display(),
-                        sol.expected_verdict
-                    )
+                        "{}:{}: {}",
+                        path.display(),
+                        problem.line,
+                        problem.description
+                    );
+                    format_problem_count += 1;
                 }
+                if dir == &indir {
+                    inputs.push((testcase, content));
+                }
+            }
+        }
+        if format_problem_count > 0 {
+            return Err(Error::BadFormat {
+                count: format_problem_count,
+            });
+        }
+
+        // Check each input with the format in creo.toml, if any.
+        let input_format = if config.input_format.is_empty() {
+            None
+        } else {
+            let input_format = InputFormat::parse(&config.input_format)
+                .map_err(|description| Error::ConfInvalid { description })?;
+            Some(input_format)
+        };
+        if let Some(input_format) = &input_format {
+            for (testcase, content) in &inputs {
+                eprintln!("Checking the format of {}", testcase.display());
+                if let Err(e) = input_format.validate(&String::from_utf8_lossy(content)) {
+                    let infile = indir.join(testcase).display().to_string();
+                    eprintln!("{}:{}:{}: {}", infile, e.line, e.column, e.message);
+                    return Err(Error::ValidationFailed {
+                        validator: INPUT_FORMAT.to_owned(),
+                        infile,
+                        inner: Box::new(e),
+                    });
+                }
+            }
+        }
+
+        // Report inputs that are the same up to whitespace.
+        // With --remove-duplicates, only the first of each group is kept.
+        inputs.sort();
+        let contents: Vec<&[u8]> = inputs
+            .iter()
+            .map(|(_, content)| content.as_slice())
+            .collect();
+        let mut duplicates = vec![];
+        for group in duplicate_groups(&contents) {
+            let sets: Vec<String> = group
+                .iter()
+                .map(|set| {
+                    let names: Vec<String> = set
+                        .iter()
+                        .map(|&i| inputs[i].0.display().to_string())
+                        .collect();
+                    names.join(" = ")
+                })
+                .collect();
+            if group.len() == 1 {
+                eprintln!("Warning: duplicate inputs: {}", sets[0]);
+            } else {
+                eprintln!(
+                    "Warning: inputs that are the same up to whitespace: {}",
+                    sets.join(", ")
+                );
+            }
+            let mut group: Vec<usize> = group.into_iter().flatten().collect();
+            group.sort();
+            duplicates.extend(group.into_iter().skip(1).map(|i| &inputs[i].0));
+        }
+        if options.remove_duplicates {
+            for testcase in duplicates {
+                eprintln!("Removing {}", testcase.display());
+                self.remove_file(&indir.join(testcase))?;
+                let outfile = outdir.join(testcase);
+                if outfile.is_file() {
+                    self.remove_file(&outfile)?;
+                }
+            }
+        }
+
+        if config.validators.is_empty() && input_format.is_none() {
+            eprintln!("No validators in creo.toml: only the format of testcases is checked");
+            return Ok(());
+        }
+
+        let mut validators = vec![];
+        for val in &config.validators {
+            let lang_config = find_language(&lang_configs, &val.language_name)?;
+            let (cd, outpath) =
+                self.compile_in_project(proj_dir, Path::new(&val.path), lang_config)?;
+            // Validate each file in `indir`.
+            // With a test overview log, the log is recorded instead of "ok", so that skipped inputs still count.
+            let kind = if val.overview_log {
+                "val-overview"
             } else {
-                eprintln!("warning");
-                let e = Error::ConfInvalid {
-                    description: format!("language not found: {}", sol.language_name),
+                "val"
+            };
+            let mut bounds_hit = BTreeMap::new();
+            for infile in self.list_dir(&indir)? {
+                if !options.matches_testcase(&infile) {
+                    continue;
+                }
+                let name = infile.display().to_string();
+                let infile = indir.join(&infile);
+                let key = self.record_key(kind, &outpath, &lang_config.run, &[], &[&infile])?;
+                let record = match self.read_record(&key)? {
+                    Some(record) if options.only_changed => {
+                        eprintln!("Skipping {} (unchanged)", name);
+                        record
+                    }
+                    _ => {
+                        eprintln!("Validating {}", name);
+                        let record = if val.overview_log {
+                            let log_dir = Path::new(CACHE_DIR).join("overview");
+                            self.mkdir_p(&log_dir)?;
+                            let log = log_dir.join(&key);
+                            let mut run = lang_config.run.clone();
+                            run.push("--testOverviewLogFileName".to_owned());
+                            run.push(log.display().to_string());
+                            self.validate(&val.path, &cd, &outpath, &run, &infile)?;
+                            let mut file = self.open_file_for_read(&log)?;
+                            self.read_from_file(&mut file)?
+                        } else {
+                            self.validate(&val.path, &cd, &outpath, &lang_config.run, &infile)?;
+                            "ok".to_owned()
+                        };
+                        self.write_record(&key, &record)?;
+                        record
+                    }
                 };
-                return Err(e);
+                if val.overview_log {
+                    for (variable, hit) in parse_overview_log(&record) {
+                        let entry: &mut BoundsHit = bounds_hit.entry(variable).or_default();
+                        *entry = entry.merge(hit);
+                    }
+                }
+            }
+            // Bounds hit by only some of the inputs say nothing about the whole testset.
+            if val.overview_log && !options.is_testcase_filtered() {
+                report_bounds_hit(val, &bounds_hit)?;
+            }
+            validators.push((val.path.as_str(), lang_config, cd, outpath));
+        }
+
+        // Every validator must reject each file in `invalid_dir`.
+        {
+            let mut accepted = vec![];
+            for infile in self.list_dir_if_exists(&invalid_dir)? {
+                let infile = invalid_dir.join(&infile);
+                if let Some(input_format) = &input_format {
+                    eprintln!(
+                        "Checking that {} rejects {}",
+                        INPUT_FORMAT,
+                        infile.display()
+                    );
+                    let mut file = self.open_file_for_read(&infile)?;
+                    let content = self.read_bytes_from_file(&mut file)?;
+                    if input_format
+                        .validate(&String::from_utf8_lossy(&content))
+                        .is_ok()
+                    {
+                        eprintln!(
+                            "{} accepted invalid input {}",
+                            INPUT_FORMAT,
+                            infile.display()
+                        );
+                        accepted.push((INPUT_FORMAT.to_owned(), infile.display().to_string()));
+                    }
+                }
+                for (path, lang_config, cd, outpath) in &validators {
+                    eprintln!("Checking that {} rejects {}", path, infile.display());
+                    match self.validate(path, cd, outpath, &lang_config.run, &infile) {
+                        Err(Error::ValidationFailed { .. }) => {}
+                        Ok(()) => {
+                            eprintln!("{} accepted invalid input {}", path, infile.display());
+                            accepted.push((path.to_string(), infile.display().to_string()));
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+            }
+            if let Some((validator, infile)) = accepted.into_iter().next() {
+                return Err(Error::InvalidInputAccepted { validator, infile });
+            }
+        }
+
+        if options.fuzz {
+            let mut testcases = self.list_dir(&indir)?;
+            testcases.retain(|infile| options.matches_testcase(infile));
+            let mut inputs = vec![];
+            for testcase in &testcases {
+                let mut file = self.open_file_for_read(&indir.join(testcase))?;
+                let content = self.read_bytes_from_file(&mut file)?;
+                inputs.push(String::from_utf8_lossy(&content).into_owned());
+            }
+            let mut invalid_inputs = out_of_bounds_inputs(&inputs);
+            // Formatting is broken only in the smallest input, which is the fastest to validate.
+            if let Some(smallest) = (0..inputs.len()).min_by_key(|&i| inputs[i].len()) {
+                invalid_inputs.extend(malformed_inputs(smallest, &inputs[smallest]));
+            }
+            let mut accepted = 0;
+            for invalid in &invalid_inputs {
+                let infile = self.save_to_cache("fuzz", invalid.content.as_bytes())?;
+                let mut rejected = input_format
+                    .as_ref()
+                    .is_some_and(|input_format| input_format.validate(&invalid.content).is_err());
+                for (path, lang_config, cd, outpath) in &validators {
+                    if rejected {
+                        break;
+                    }
+                    match self.validate(path, cd, outpath, &lang_config.run, &infile) {
+                        Err(Error::ValidationFailed { .. }) => rejected = true,
+                        result => result?,
+                    }
+                }
+                if !rejected {
+                    eprintln!(
+                        "Warning: every validator accepts {} with {}",
+                        testcases[invalid.source].display(),
+                        invalid.description
+                    );
+                    accepted += 1;
+                }
+            }
+            eprintln!(
+                "Fuzzing complete: {} of {} broken inputs were accepted",
+                accepted,
+                invalid_inputs.len()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn show_diff(&mut self, proj_dir: &str, solution: &str, testcase: &str) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let lang_configs = config.languages;
+        let TestcaseConfig { indir, outdir, .. } = config.testcase_config;
+        let indir = proj_dir.join(indir);
+        let outdir = proj_dir.join(outdir);
+
+        let sol = find_solution(&config.solutions, solution)?;
+        let infile = indir.join(testcase);
+        let outfile = outdir.join(testcase);
+
+        let lang_config = find_language(&lang_configs, &sol.language_name)?;
+        let (cd, outpath) = self.compile_in_project(proj_dir, Path::new(&sol.path), lang_config)?;
+        let actual = self.run_with_input(&cd, &outpath, &lang_config.run, &infile)?;
+        let mut file = self.open_file_for_read(&outfile)?;
+        let expected = self.read_bytes_from_file(&mut file)?;
+        if expected == actual {
+            eprintln!("No difference: {} on {}", sol.path, infile.display());
+        } else {
+            print!(
+                "{}",
+                unified_diff(
+                    &expected,
+                    &actual,
+                    &outfile.display().to_string(),
+                    &format!("{} < {}", sol.path, infile.display()),
+                )
+            );
+        }
+
+        Ok(())
+    }
+
+    fn cross_check(&mut self, proj_dir: &str, options: &RunOptions) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let limits = limits(&config);
+        let lang_configs = config.languages;
+        let indir = proj_dir.join(config.testcase_config.indir);
+
+        // Compile all solutions expected to be AC
+        let mut targets = vec![];
+        for sol in &config.solutions {
+            if sol.expected_verdict != Verdict::AC || !options.matches_solution(&sol.path) {
+                continue;
+            }
+            let lang_config = find_language(&lang_configs, &sol.language_name)?;
+            let (cd, outpath) =
+                self.compile_in_project(proj_dir, Path::new(&sol.path), lang_config)?;
+            targets.push((sol, lang_config, cd, outpath));
+        }
+        if targets.len() < 2 {
+            return Err(Error::ConfInvalid {
+                description: format!(
+                    "Cross-checking needs two or more solutions expected to be AC, but found {}",
+                    targets.len()
+                ),
+            });
+        }
+        let solutions: Vec<&SolutionConfig> = targets.iter().map(|&(sol, ..)| sol).collect();
+        let mut testcases = self.list_dir(&indir)?;
+        testcases.retain(|infile| options.matches_testcase(infile));
+
+        // All solutions run on a testcase before the next testcase, so that each testcase is compared as soon as possible.
+        let tasks: Vec<(&Path, usize)> = testcases
+            .iter()
+            .flat_map(|infile| (0..targets.len()).map(move |target| (infile.as_path(), target)))
+            .collect();
+        // Outputs on the current testcase, or verdicts of the solutions that fail on it
+        let mut outputs = vec![];
+        let mut disagreements = 0;
+        let mut error = None;
+        let this = &*self;
+        run_in_order(
+            &tasks,
+            options.workers(),
+            |worker, _, &(infile, target)| -> Result<RunResult> {
+                let (_, lang_config, cd, outpath) = &targets[target];
+                let limits = Limits {
+                    cpu: options.cpu_for(worker),
+                    ..limits.clone()
+                };
+                this.run_with_limits(cd, outpath, &lang_config.run, &indir.join(infile), &limits)
+            },
+            |index, result| {
+                let (infile, target) = tasks[index];
+                match result {
+                    Ok(result) => outputs.push(match failure_verdict(&result, &limits) {
+                        Some(verdict) => Err(verdict),
+                        None => Ok(result.stdout),
+                    }),
+                    Err(e) => {
+                        eprintln!("Running {} failed: {}", infile.display(), e);
+                        error.get_or_insert(e);
+                    }
+                }
+                if target + 1 < targets.len() {
+                    return;
+                }
+                let outputs = std::mem::take(&mut outputs);
+                if outputs.len() < targets.len() {
+                    return;
+                }
+                if report_disagreement(infile, &solutions, &outputs) {
+                    disagreements += 1;
+                } else {
+                    eprintln!("All solutions agree on {}", infile.display());
+                }
+            },
+        );
+        if let Some(e) = error {
+            return Err(e);
+        }
+        if disagreements > 0 {
+            return Err(Error::SolutionsDisagree {
+                count: disagreements,
+            });
+        }
+        Ok(())
+    }
+    fn run_solution(&mut self, proj_dir: &str, solution: &str, input: Option<&str>) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let lang_configs = &config.languages;
+        let TestcaseConfig { indir, outdir, .. } = &config.testcase_config;
+        let indir = proj_dir.join(indir);
+        let outdir = proj_dir.join(outdir);
+        let limits = limits(&config);
+
+        let sol = find_solution(&config.solutions, solution)?;
+        let lang_config = find_language(lang_configs, &sol.language_name)?;
+        let (cd, outpath) = self.compile_in_project(proj_dir, Path::new(&sol.path), lang_config)?;
+
+        // A testcase name in `indir` is preferred to a path.
+        // Without an input, the standard input is used.
+        let (infile, outfile) = match input {
+            Some(input) => {
+                let in_testcase = indir.join(input);
+                if in_testcase.is_file() {
+                    (in_testcase, Some(outdir.join(input)))
+                } else {
+                    (PathBuf::from(input), None)
+                }
+            }
+            None => {
+                let mut content = vec![];
+                std::io::stdin().read_to_end(&mut content)?;
+                (self.save_to_cache("stdin", &content)?, None)
+            }
+        };
+
+        let result = self.run_with_limits(&cd, &outpath, &lang_config.run, &infile, &limits)?;
+        std::io::stdout().write_all(&result.stdout)?;
+        std::io::stderr().write_all(&result.stderr)?;
+        eprintln!("Resources: {}", result.resources());
+        match outfile {
+            Some(outfile) if outfile.is_file() => {
+                let (verdict, mismatch) = self.get_verdict(&result, &limits, &outfile)?;
+                eprintln!(
+                    "Verdict: {:?} (expected output: {})",
+                    verdict,
+                    outfile.display()
+                );
+                if let Some(mismatch) = mismatch {
+                    eprint!("{}", mismatch);
+                }
+            }
+            _ => match failure_verdict(&result, &limits) {
+                Some(verdict) => eprintln!("Verdict: {:?}", verdict),
+                None => eprintln!("Verdict: unknown (no expected output)"),
+            },
+        }
+
+        Ok(())
+    }
+
+    fn stress(&mut self, proj_dir: &str, options: &StressOptions) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let lang_configs = &config.languages;
+        let limits = limits(&config);
+
+        // Compile the generator, validators and solutions
+        let (gen_path, gen_args) = options.generator.split_first().unwrap();
+        let gen_lang = find_generator_language(&config, gen_path)?;
+        let (gen_cd, gen_outpath) =
+            self.compile_in_project(proj_dir, Path::new(gen_path), gen_lang)?;
+        let mut gen_run = gen_lang.run.clone();
+        gen_run.extend(gen_args.iter().cloned());
+        let mut validators = vec![];
+        for val in &config.validators {
+            let lang_config = find_language(lang_configs, &val.language_name)?;
+            let (cd, outpath) =
+                self.compile_in_project(proj_dir, Path::new(&val.path), lang_config)?;
+            validators.push((val, lang_config, cd, outpath));
+        }
+        let mut paths: Vec<&str> = options.solutions.iter().map(|x| x.as_str()).collect();
+        if paths.len() == 1 {
+            check_reference_solution(&config)?;
+            let reference = config
+                .solutions
+                .iter()
+                .find(|&solution| solution.is_reference_solution)
+                .unwrap();
+            paths.insert(0, &reference.path);
+        }
+        let mut solutions = vec![];
+        for path in paths {
+            let sol = find_solution(&config.solutions, path)?;
+            let lang_config = find_language(lang_configs, &sol.language_name)?;
+            let (cd, outpath) =
+                self.compile_in_project(proj_dir, Path::new(&sol.path), lang_config)?;
+            solutions.push((sol, lang_config, cd, outpath));
+        }
+        // The generator reads nothing from the standard input.
+        let empty = self.save_to_cache("stress", b"")?;
+
+        let mut seed = options.seed;
+        let mut iterations = 0;
+        loop {
+            if let Some(max_iterations) = options.iterations {
+                if iterations >= max_iterations {
+                    eprintln!("No counterexample found in {} runs", max_iterations);
+                    return Ok(());
+                }
+            }
+            iterations += 1;
+
+            let mut run = gen_run.clone();
+            run.push(seed.to_string());
+            let result = self.run_with_limits(&gen_cd, &gen_outpath, &run, &empty, &limits)?;
+            if let Some(verdict) = failure_verdict(&result, &limits) {
+                return Err(Error::RunFailed {
+                    path: gen_path.clone(),
+                    infile: format!("(seed = {})", seed),
+                    verdict,
+                });
+            }
+            let input = result.stdout;
+            let infile = self.save_to_cache("stress", &input)?;
+            for (val, lang_config, cd, outpath) in &validators {
+                self.validate(&val.path, cd, outpath, &lang_config.run, &infile)?;
+            }
+
+            let mut results = vec![];
+            for (_, lang_config, cd, outpath) in &solutions {
+                let result =
+                    self.run_with_limits(cd, outpath, &lang_config.run, &infile, &limits)?;
+                results.push(result);
+            }
+            let verdicts: Vec<Option<Verdict>> = results
+                .iter()
+                .map(|result| failure_verdict(result, &limits))
+                .collect();
+            let mismatch = if verdicts.iter().all(|verdict| verdict.is_none()) {
+                first_mismatch(&results[0].stdout, &results[1].stdout)
+            } else {
+                None
+            };
+            if mismatch.is_none() && verdicts.iter().all(|verdict| verdict.is_none()) {
+                eprintln!("Seed {}: outputs agree", seed);
+                seed = seed.wrapping_add(1);
+                continue;
+            }
+
+            // Save the counterexample
+            let path = proj_dir
+                .join(&options.counterexample_dir)
+                .join(format!("stress-{}.txt", seed));
+            self.overwrite_file(&path, &input)?;
+            eprintln!(
+                "Counterexample found (seed = {}), saved to {}",
+                seed,
+                path.display()
+            );
+            for ((sol, ..), verdict) in solutions.iter().zip(&verdicts) {
+                match verdict {
+                    Some(verdict) => eprintln!("  {}: {:?}", sol.path, verdict),
+                    None => eprintln!("  {}: finished normally", sol.path),
+                }
+            }
+            if let Some(mismatch) = mismatch {
+                eprintln!(
+                    "Outputs differ (expected: {}, actual: {})",
+                    solutions[0].0.path, solutions[1].0.path
+                );
+                eprint!("{}", mismatch);
+            }
+            return Ok(());
+        }
+    }
+
+    fn minimize(
+        &mut self,
+        proj_dir: &str,
+        solution: &str,
+        testcase: &str,
+        counterexample_dir: &str,
+    ) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let lang_configs = &config.languages;
+        let TestcaseConfig { indir, .. } = &config.testcase_config;
+        let indir = proj_dir.join(indir);
+        let limits = limits(&config);
+        check_reference_solution(&config)?;
+
+        // Compile the validators, the reference solution and the solution
+        let mut validators = vec![];
+        for val in &config.validators {
+            let lang_config = find_language(lang_configs, &val.language_name)?;
+            let (cd, outpath) =
+                self.compile_in_project(proj_dir, Path::new(&val.path), lang_config)?;
+            validators.push((val.path.as_str(), lang_config, cd, outpath));
+        }
+        let mut programs = vec![];
+        let reference = config
+            .solutions
+            .iter()
+            .find(|&solution| solution.is_reference_solution)
+            .unwrap();
+        for sol in [reference, find_solution(&config.solutions, solution)?] {
+            let lang_config = find_language(lang_configs, &sol.language_name)?;
+            let (cd, outpath) =
+                self.compile_in_project(proj_dir, Path::new(&sol.path), lang_config)?;
+            programs.push((sol.path.as_str(), lang_config, cd, outpath));
+        }
+        let (reference, solution) = (&programs[0], &programs[1]);
+
+        // A testcase name in `indir` is preferred to a path.
+        let infile = match indir.join(testcase) {
+            in_testcase if in_testcase.is_file() => in_testcase,
+            _ => PathBuf::from(testcase),
+        };
+        let mut file = self.open_file_for_read(&infile)?;
+        let mut current =
+            String::from_utf8_lossy(&self.read_bytes_from_file(&mut file)?).to_string();
+        let verdict = match self.verdict_on_input(
+            &validators,
+            reference,
+            solution,
+            &limits,
+            current.as_bytes(),
+        )? {
+            Some(Verdict::AC) => {
+                eprintln!(
+                    "{} gets AC on {}: nothing to minimize",
+                    solution.0,
+                    infile.display()
+                );
+                return Ok(());
+            }
+            Some(verdict) => verdict,
+            None => {
+                eprintln!(
+                    "{} is invalid, or the reference solution fails on it",
+                    infile.display()
+                );
+                return Ok(());
+            }
+        };
+        eprintln!(
+            "{} gets {:?} on {} ({} bytes)",
+            solution.0,
+            verdict,
+            infile.display(),
+            current.len()
+        );
+
+        // Take the first smaller candidate that keeps the verdict, until no candidate does.
+        'shrink: loop {
+            for candidate in candidates(&current) {
+                if !is_smaller(&candidate, &current) {
+                    continue;
+                }
+                let candidate_verdict = self.verdict_on_input(
+                    &validators,
+                    reference,
+                    solution,
+                    &limits,
+                    candidate.as_bytes(),
+                )?;
+                if candidate_verdict.as_ref() == Some(&verdict) {
+                    current = candidate;
+                    eprintln!("Shrunk to {} bytes", current.len());
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+
+        let name = infile.file_name().unwrap_or_default().to_string_lossy();
+        let path = proj_dir
+            .join(counterexample_dir)
+            .join(format!("minimized-{}", name));
+        self.overwrite_file(&path, current.as_bytes())?;
+        eprintln!("The minimized input is saved to {}", path.display());
+
+        Ok(())
+    }
+
+    fn mutate(
+        &mut self,
+        proj_dir: &str,
+        max_mutants: Option<usize>,
+        options: &RunOptions,
+    ) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let lang_configs = &config.languages;
+        let TestcaseConfig { indir, outdir, .. } = &config.testcase_config;
+        let indir = proj_dir.join(indir);
+        let outdir = proj_dir.join(outdir);
+        let limits = limits(&config);
+        check_reference_solution(&config)?;
+        let reference = config
+            .solutions
+            .iter()
+            .find(|&solution| solution.is_reference_solution)
+            .unwrap();
+        let lang_config = find_language(lang_configs, &reference.language_name)?;
+
+        let src = self.to_absolute(&proj_dir.join(&reference.path))?;
+        let cd = src.join("..").clean();
+        let mut file = self.open_file_for_read(&src)?;
+        let original = self.read_from_file(&mut file)?;
+        let mut mutants = mutants(&original);
+        if let Some(max_mutants) = max_mutants {
+            mutants.truncate(max_mutants);
+        }
+        let ext = src
+            .extension()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let testcases = self.list_dir(&indir)?;
+
+        let (mut killed, mut stillborn, mut survived) = (0, 0, vec![]);
+        for (index, mutant) in mutants.iter().enumerate() {
+            let name = format!(
+                "mutant {}/{} (line {}: {})",
+                index + 1,
+                mutants.len(),
+                mutant.line,
+                mutant.description
+            );
+            // Mutants are written next to the reference solution, so that relative includes work.
+            // The file is removed right after compiling.
+            let mutant_src = cd
+                .join(format!(
+                    ".creo-mutant-{}",
+                    sha256_hex(&[mutant.source.as_bytes()])
+                ))
+                .with_extension(&ext);
+            self.overwrite_file(&mutant_src, mutant.source.as_bytes())?;
+            let compiled = self.compile(&cd, &mutant_src, &lang_config.compile);
+            self.remove_file(&mutant_src)?;
+            let outpath = match compiled {
+                Ok(outpath) => outpath,
+                Err(_) => {
+                    eprintln!("{}: does not compile", name);
+                    stillborn += 1;
+                    continue;
+                }
+            };
+
+            // Runs stop once a testcase catches the mutant.
+            let caught = AtomicBool::new(false);
+            let mut catcher = None;
+            let this = &*self;
+            run_in_order(
+                &testcases,
+                options.workers(),
+                |worker, _, infile| -> Result<Option<Verdict>> {
+                    if caught.load(Ordering::SeqCst) {
+                        return Ok(None);
+                    }
+                    let outfile = outdir.join(infile);
+                    let infile = indir.join(infile);
+                    let limits = Limits {
+                        cpu: options.cpu_for(worker),
+                        ..limits.clone()
+                    };
+                    let result =
+                        this.run_with_limits(&cd, &outpath, &lang_config.run, &infile, &limits)?;
+                    let (verdict, _) = this.get_verdict(&result, &limits, &outfile)?;
+                    if verdict != Verdict::AC {
+                        caught.store(true, Ordering::SeqCst);
+                    }
+                    Ok(Some(verdict))
+                },
+                |index, outcome| {
+                    if catcher.is_some() {
+                        return;
+                    }
+                    match outcome {
+                        Ok(Some(Verdict::AC)) | Ok(None) => {}
+                        Ok(Some(verdict)) => catcher = Some(Ok((index, verdict))),
+                        Err(e) => catcher = Some(Err(e)),
+                    }
+                },
+            );
+            match catcher {
+                Some(Ok((index, verdict))) => {
+                    eprintln!(
+                        "{}: caught by {} ({:?})",
+                        name,
+                        testcases[index].display(),
+                        verdict
+                    );
+                    killed += 1;
+                }
+                Some(Err(e)) => return Err(e),
+                None => {
+                    eprintln!("{}: survived", name);
+                    survived.push(mutant);
+                }
+            }
+        }
+
+        for mutant in &survived {
+            println!(
+                "Surviving mutant at line {}: {}",
+                mutant.line, mutant.description
+            );
+            print!(
+                "{}",
+                unified_diff(
+                    original.as_bytes(),
+                    mutant.source.as_bytes(),
+                    &reference.path,
+                    "mutant",
+                )
+            );
+        }
+        eprintln!(
+            "{} mutants: {} caught, {} survived, {} did not compile",
+            mutants.len(),
+            killed,
+            survived.len(),
+            stillborn
+        );
+
+        Ok(())
+    }
+
+    fn coverage(&mut self, proj_dir: &str) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let lang_configs = &config.languages;
+        let TestcaseConfig { indir, .. } = &config.testcase_config;
+        let indir = proj_dir.join(indir);
+        // Instrumented binaries are slower, and runs are not judged.
+        let limits = Limits {
+            time_limit: config.time_limit * COVERAGE_TIME_LIMIT_FACTOR,
+            ..limits(&config)
+        };
+        check_reference_solution(&config)?;
+        let reference = config
+            .solutions
+            .iter()
+            .find(|&solution| solution.is_reference_solution)
+            .unwrap();
+        let lang_config = find_language(lang_configs, &reference.language_name)?;
+        let coverage = lang_config
+            .coverage
+            .as_ref()
+            .ok_or_else(|| Error::ConfInvalid {
+                description: format!(
+                    "coverage is not configured for language: {}",
+                    lang_config.language_name
+                ),
+            })?;
+
+        // Build in a fresh directory, so that no coverage data is left from earlier runs.
+        let src = self.to_absolute(&proj_dir.join(&reference.path))?;
+        let cd = src.join("..").clean();
+        let mut file = self.open_file_for_read(&src)?;
+        let content = self.read_bytes_from_file(&mut file)?;
+        let chunks: Vec<&[u8]> = coverage
+            .compile
+            .iter()
+            .map(|c| c.as_bytes())
+            .chain([content.as_slice()])
+            .collect();
+        let workdir = Path::new(CACHE_DIR)
+            .join("coverage")
+            .join(sha256_hex(&chunks));
+        self.remove_dir_all(&workdir)?;
+        self.mkdir_p(&workdir)?;
+        let outpath = workdir.join("a.out");
+        let compile = self.build_command(&coverage.compile, &src, &outpath);
+        self.run_once(&cd, &outpath, &compile)?;
+
+        for infile in self.list_dir(&indir)? {
+            eprintln!("Running {}", infile.display());
+            let infile = indir.join(&infile);
+            let result = self.run_with_limits(&cd, &outpath, &lang_config.run, &infile, &limits)?;
+            if let Some(verdict) = failure_verdict(&result, &limits) {
+                eprintln!("Running {} failed: {:?}", infile.display(), verdict);
+            }
+        }
+
+        let empty = self.save_to_cache("stdin", b"")?;
+        let report = self.run_with_limits(&workdir, &outpath, &coverage.report, &empty, &limits)?;
+        if !report.success() {
+            std::io::stderr().write_all(&report.stderr)?;
+            return Err(Error::RunFailed {
+                path: coverage.report.join(" "),
+                infile: empty.display().to_string(),
+                verdict: Verdict::RE,
+            });
+        }
+        let source_name = src.file_name().unwrap_or_default().to_string_lossy();
+        let lines = parse_gcov(&String::from_utf8_lossy(&report.stdout), &source_name);
+        let executable = lines.iter().filter(|line| line.count.is_some()).count();
+        let mut covered = 0;
+        for line in &lines {
+            match line.count {
+                Some(0) => println!("{}:{}: {}", reference.path, line.line, line.source),
+                Some(_) => covered += 1,
+                None => {}
+            }
+        }
+        eprintln!(
+            "{} of {} executable lines in {} ran",
+            covered, executable, reference.path
+        );
+
+        Ok(())
+    }
+    fn stats(&mut self, proj_dir: &str) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        let reference = config
+            .solutions
+            .iter()
+            .find(|sol| sol.is_reference_solution);
+        let (total, by_generator) = self.testset_stats(proj_dir, &config)?;
+
+        println!("Tests: {}", total.tests);
+        for (title, size, largest) in [
+            ("Input", total.input_size, &total.largest_input),
+            ("Output", total.output_size, &total.largest_output),
+        ] {
+            match largest {
+                Some((largest, name)) => println!(
+                    "{}: total {}, largest {} ({})",
+                    title,
+                    format_size(size),
+                    format_size(*largest),
+                    name
+                ),
+                None => println!("{}: total {}", title, format_size(size)),
+            }
+        }
+        match (reference, &total.slowest) {
+            (Some(sol), Some((slowest, name))) => println!(
+                "Judging time of an AC submission (recorded runs of {}): total {:.3} s, slowest {:.3} s ({})",
+                sol.path, total.time, slowest, name
+            ),
+            _ => println!("Judging time of an AC submission: unknown"),
+        }
+        if total.untimed > 0 {
+            println!(
+                "  No recorded time for {} tests: run `creo refgen` or `creo test` to measure them",
+                total.untimed
+            );
+        }
+        println!(
+            "Judging time of a submission that times out on every test: up to {:.1} s",
+            config.time_limit * total.tests as f64
+        );
+        println!("By generator:");
+        for (generator, stats) in &by_generator {
+            println!("  {}: {}", generator, stats.summary());
+        }
+        for warning in judge_limit_warnings(&total, &config.judge_limits) {
+            eprintln!("Warning: {}", warning);
+        }
+        Ok(())
+    }
+    fn constants(&mut self, proj_dir: &str, statement: Option<&str>) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        let task_dir = proj_dir.join(TASK_DIR);
+
+        // With a statement, print it with the constants substituted.
+        // Without one, check that every statement uses only defined constants.
+        if let Some(statement) = statement {
+            let text = self.substitute_constants(&task_dir.join(statement), &config)?;
+            print!("{}", text);
+            return Ok(());
+        }
+        for statement in self.list_dir(&task_dir)? {
+            if statement.extension().is_some_and(|ext| ext == "md") {
+                self.substitute_constants(&task_dir.join(&statement), &config)?;
+                eprintln!("{}: OK", statement.display());
+            }
+        }
+        Ok(())
+    }
+    fn statement(&mut self, proj_dir: &str) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        let samples = self.read_samples(proj_dir, &config)?;
+        let task_dir = proj_dir.join(TASK_DIR);
+
+        for statement in self.list_dir(&task_dir)? {
+            if statement.extension().map_or(true, |ext| ext != "md") {
+                continue;
+            }
+            let markdown = self.substitute_constants(&task_dir.join(&statement), &config)?;
+            let lang = statement.file_stem().unwrap().to_string_lossy();
+            // A statement with samples of its own doesn't need them again.
+            let samples = if sample_blocks(&markdown).is_empty() {
+                &samples[..]
+            } else {
+                &[]
+            };
+            let html = statement::render(
+                &markdown,
+                &lang,
+                config.time_limit,
+                config.memory_limit,
+                samples,
+            );
+            let htmlfile = task_dir.join(&statement).with_extension("html");
+            self.overwrite_file(&htmlfile, html.as_bytes())?;
+            eprintln!(
+                "Rendered {} with {} samples into {}",
+                statement.display(),
+                samples.len(),
+                htmlfile.display()
+            );
+        }
+        Ok(())
+    }
+    fn check_samples(&mut self, proj_dir: &str) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        self.write_constants(proj_dir, &config)?;
+        check_reference_solution(&config)?;
+        let TestcaseConfig { indir, outdir, .. } = &config.testcase_config;
+        let indir = proj_dir.join(indir);
+        let outdir = proj_dir.join(outdir);
+        let task_dir = proj_dir.join(TASK_DIR);
+        let limits = limits(&config);
+        let testcases = self.sample_testcases(proj_dir, &config)?;
+
+        let reference = config
+            .solutions
+            .iter()
+            .find(|&solution| solution.is_reference_solution)
+            .unwrap();
+        let lang_config = find_language(&config.languages, &reference.language_name)?;
+        let (cd, outpath) =
+            self.compile_in_project(proj_dir, Path::new(&reference.path), lang_config)?;
+
+        let mut statements = self.list_dir(&task_dir)?;
+        statements.retain(|statement| statement.extension().is_some_and(|ext| ext == "md"));
+        statements.sort();
+        let mut mismatches = 0;
+        for statement in statements {
+            let path = task_dir.join(&statement);
+            let markdown = self.substitute_constants(&path, &config)?;
+            let blocks = sample_blocks(&markdown);
+            if blocks.is_empty() {
+                eprintln!("{}: no samples of its own", path.display());
+                continue;
+            }
+            let [inputs, outputs]: [Vec<&SampleBlock>; 2] = [SampleKind::Input, SampleKind::Output]
+                .map(|kind| blocks.iter().filter(|block| block.kind == kind).collect());
+
+            // The i-th sample in a statement should be the same as the i-th sample testcase.
+            for (blocks, dir, kind) in [(&inputs, &indir, "input"), (&outputs, &outdir, "output")] {
+                if blocks.len() != testcases.len() {
+                    eprintln!(
+                        "{}: {} sample {}s, but {} testcases are samples",
+                        path.display(),
+                        blocks.len(),
+                        kind,
+                        testcases.len()
+                    );
+                    mismatches += 1;
+                }
+                for (i, (block, testcase)) in blocks.iter().zip(&testcases).enumerate() {
+                    let file = dir.join(testcase);
+                    let Some(content) = self.read_file_if_exists(&file)? else {
+                        eprintln!(
+                            "{}:{}: sample {} {}: {} doesn't exist",
+                            path.display(),
+                            block.line,
+                            kind,
+                            i + 1,
+                            file.display()
+                        );
+                        mismatches += 1;
+                        continue;
+                    };
+                    if let Some(mismatch) = first_mismatch(&content, block.content.as_bytes()) {
+                        eprint!(
+                            "{}:{}: sample {} {} differs from {}: {}",
+                            path.display(),
+                            block.line + mismatch.line - 1,
+                            kind,
+                            i + 1,
+                            file.display(),
+                            mismatch
+                        );
+                        mismatches += 1;
+                    }
+                }
+            }
+
+            // The reference solution should print the sample output on the sample input.
+            for (i, (input, output)) in inputs.iter().zip(&outputs).enumerate() {
+                let infile = self.save_to_cache("sample", input.content.as_bytes())?;
+                let outfile = self.save_to_cache("sample", output.content.as_bytes())?;
+                let result =
+                    self.run_with_limits(&cd, &outpath, &lang_config.run, &infile, &limits)?;
+                let (verdict, mismatch) = self.get_verdict(&result, &limits, &outfile)?;
+                if verdict == Verdict::AC {
+                    continue;
+                }
+                let line = match &mismatch {
+                    Some(mismatch) => output.line + mismatch.line - 1,
+                    None => input.line,
+                };
+                eprint!(
+                    "{}:{}: {} gets {:?} on sample {}",
+                    path.display(),
+                    line,
+                    reference.path,
+                    verdict,
+                    i + 1
+                );
+                match mismatch {
+                    Some(mismatch) => eprint!(": {}", mismatch),
+                    None => eprintln!(),
+                }
+                mismatches += 1;
+            }
+        }
+        if mismatches > 0 {
+            return Err(Error::SampleMismatch { count: mismatches });
+        }
+        eprintln!(
+            "Samples in statements match the testset and {}",
+            reference.path
+        );
+        Ok(())
+    }
+    fn check_translations(&mut self, proj_dir: &str, accept: bool) -> Result<()> {
+        let proj_dir = Path::new(proj_dir);
+
+        // Read the config file
+        let config = self.read_config(proj_dir)?;
+        let task_dir = proj_dir.join(TASK_DIR);
+
+        let mut statements = self.list_dir(&task_dir)?;
+        statements.retain(|statement| statement.extension().is_some_and(|ext| ext == "md"));
+        statements.sort();
+        let mut outlines = vec![];
+        for statement in &statements {
+            let markdown = self.substitute_constants(&task_dir.join(statement), &config)?;
+            outlines.push((statement.display().to_string(), outline(&markdown)));
+        }
+
+        // Every translation is compared with the first one.
+        let mut differences = 0;
+        if let Some((first, rest)) = outlines.split_first() {
+            for other in rest {
+                for difference in
+                    translation_differences((&first.0, &first.1), (&other.0, &other.1))
+                {
+                    eprintln!("{}", difference);
+                    differences += 1;
+                }
+            }
+        }
+
+        // Statements whose hash differs from the accepted one have changed since the translations were last in sync.
+        // If only some of them have changed, the others may need updating.
+        // The accepted hashes are kept in the project, so that they are shared with everyone who works on it.
+        let accepted_file = task_dir.join(TRANSLATIONS_FILE);
+        let accepted: BTreeMap<String, String> = match self.read_file_if_exists(&accepted_file)? {
+            Some(content) => toml::from_str(&String::from_utf8_lossy(&content))?,
+            None => BTreeMap::new(),
+        };
+        let mut hashes = BTreeMap::new();
+        let mut changed = vec![];
+        let mut unchanged = vec![];
+        let mut unaccepted = vec![];
+        for statement in &statements {
+            let name = statement.display().to_string();
+            let hash = self
+                .hash_file_if_exists(&task_dir.join(statement))?
+                .unwrap_or_default();
+            match accepted.get(&name) {
+                Some(recorded) if *recorded == hash => unchanged.push(name.clone()),
+                Some(_) => changed.push(name.clone()),
+                None => unaccepted.push(name.clone()),
+            }
+            hashes.insert(name, hash);
+        }
+        for statement in &unaccepted {
+            eprintln!(
+                "Warning: {} has never been accepted as in sync with the other translations",
+                statement
+            );
+        }
+        if !unchanged.is_empty() {
+            for statement in &changed {
+                eprintln!(
+                    "Warning: {} has changed since {} was last updated",
+                    statement,
+                    unchanged.join(", ")
+                );
+            }
+        }
+
+        if differences > 0 {
+            return Err(Error::TranslationsDiffer { count: differences });
+        }
+        if !accept {
+            if changed.is_empty() && unaccepted.is_empty() {
+                eprintln!("{} translations are in sync", statements.len());
+            } else {
+                eprintln!(
+                    "Update the other translations, then run with --accept to record them as in sync"
+                );
+            }
+            return Ok(());
+        }
+        let content = format!(
+            "# Hashes of the statements when `creo translations --accept` last recorded them as in sync.\n{}",
+            toml::to_string(&hashes)?
+        );
+        self.overwrite_file(&accepted_file, content.as_bytes())?;
+        eprintln!(
+            "Recorded {} translations as in sync in {}",
+            statements.len(),
+            accepted_file.display()
+        );
+        Ok(())
+    }
+}
+
+// How many times longer than the time limit an instrumented binary may run.
+const COVERAGE_TIME_LIMIT_FACTOR: f64 = 5.0;
+
+// Directory of the statements, relative to the project directory.
+const TASK_DIR: &str = "task";
+
+// File in TASK_DIR with the hashes of the statements accepted by `creo translations --accept`.
+const TRANSLATIONS_FILE: &str = "translations.toml";
+
+// How the input format in creo.toml is named in logs and errors, as if it were a validator.
+const INPUT_FORMAT: &str = "input_format";
+
+pub struct ProjectImpl;
+
+impl IoUtilExt for ProjectImpl {}
+impl RunUtilExt for ProjectImpl {}
+impl ProjectExt for ProjectImpl {}
+
+// Finds the configuration of a language by its name.
+fn find_language<'a>(
+    lang_configs: &'a [LanguageConfig],
+    language_name: &str,
+) -> Result<&'a LanguageConfig> {
+    lang_configs
+        .iter()
+        .find(|&c| c.language_name == language_name)
+        .ok_or_else(|| Error::ConfInvalid {
+            description: format!("language not found: {}", language_name),
+        })
+}
+
+// Finds the language of a generator.
+// If the generator is not in creo.toml, the language is determined by the file extension.
+fn find_generator_language<'a>(config: &'a CreoConfig, path: &str) -> Result<&'a LanguageConfig> {
+    if let Some(gen) = config
+        .generators
+        .iter()
+        .find(|gen| gen.path == Path::new(path))
+    {
+        return find_language(&config.languages, &gen.language_name);
+    }
+    let ext = Path::new(path).extension();
+    config
+        .languages
+        .iter()
+        .find(|&c| ext == Some(&OsString::from(c.target_ext.clone())))
+        .ok_or_else(|| Error::ConfInvalid {
+            description: format!("language not found for generator: {}", path),
+        })
+}
+
+// Finds a solution by its path as written in creo.toml.
+fn find_solution<'a>(solutions: &'a [SolutionConfig], path: &str) -> Result<&'a SolutionConfig> {
+    solutions
+        .iter()
+        .find(|&sol| sol.path == path)
+        .ok_or_else(|| Error::ConfInvalid {
+            description: format!("solution not found: {}", path),
+        })
+}
+
+fn limits(config: &CreoConfig) -> Limits {
+    Limits {
+        time_limit: config.time_limit,
+        memory_limit: config.memory_limit,
+        cpu: None,
+    }
+}
+
+// Warns about bounds of validator variables that no input reaches.
+// Returns Error::BoundNotHit if one of them is required in creo.toml.
+fn report_bounds_hit(
+    val: &ValidatorConfig,
+    bounds_hit: &BTreeMap<String, BoundsHit>,
+) -> Result<()> {
+    for (variable, hit) in bounds_hit {
+        for (bound, hit) in [(Bound::Min, hit.min), (Bound::Max, hit.max)] {
+            if !hit {
+                eprintln!(
+                    "Warning: no input has {} at its {} ({})",
+                    variable, bound, val.path
+                );
             }
         }
-
-        Ok(())
     }
+    for (variable, bounds) in &val.required_bounds {
+        let hit = bounds_hit.get(variable).copied().unwrap_or_default();
+        for &bound in bounds {
+            let hit = match bound {
+                Bound::Min => hit.min,
+                Bound::Max => hit.max,
+            };
+            if !hit {
+                return Err(Error::BoundNotHit {
+                    validator: val.path.clone(),
+                    variable: variable.clone(),
+                    bound,
+                });
+            }
+        }
+    }
+    Ok(())
+}
 
-    fn val(&mut self, proj_dir: &str) -> Result<()> {
-        let proj_dir = Path::new(proj_dir);
+// Finds the verdict of a run that did not finish normally.
+// Returns None if the output should be checked.
+fn failure_verdict(result: &RunResult, limits: &Limits) -> Option<Verdict> {
+    if result.timed_out {
+        Some(Verdict::TLE)
+    } else if result.exceeds_memory(limits) {
+        Some(Verdict::MLE)
+    } else if !result.success() {
+        Some(Verdict::RE)
+    } else {
+        None
+    }
+}
 
-        // Read the config file
-        let config = self.read_config(proj_dir)?;
-        let lang_configs = config.languages;
-        let TestcaseConfig { indir, .. } = config.testcase_config;
-        let indir = proj_dir.join(indir);
+// The outcome of running a solution on a testcase in Project::test.
+enum TestOutcome {
+    // Not run because the verdict of the solution is already settled.
+    Skipped,
+    // Not run because the same run was recorded.
+    Recorded(Verdict),
+    Ran(Verdict, Option<Mismatch>),
+}
 
-        if config.validators.is_empty() {
-            return Err(Error::ConfInvalid {
-                description: "Validators not found in creo.toml".to_owned(),
-            });
+// Prints how the outputs of solutions on a testcase differ, if they do.
+// Each output is either what a solution printed or the verdict of a solution that failed.
+// Returns true if they differ.
+fn report_disagreement(
+    infile: &Path,
+    solutions: &[&SolutionConfig],
+    outputs: &[std::result::Result<Vec<u8>, Verdict>],
+) -> bool {
+    // Solutions grouped by their outputs
+    let mut groups: Vec<(_, Vec<&SolutionConfig>)> = vec![];
+    for (&sol, output) in solutions.iter().zip(outputs) {
+        match groups.iter_mut().find(|(x, _)| *x == output) {
+            Some((_, sols)) => sols.push(sol),
+            None => groups.push((output, vec![sol])),
         }
-
-        for val in config.validators {
-            let src = proj_dir.join(&val.path);
-            let cd = src.join("..").clean();
-            let cd = self.to_absolute(&cd)?;
-            let lang_config = lang_configs
-                .iter()
-                .find(|&c| c.language_name == val.language_name);
-            if let Some(x) = lang_config {
-                let outpath = self.compile(&cd, &self.to_absolute(&src)?, &x.compile)?;
-                // Validate each file in `indir`.
-                for infile in self.list_dir(&indir)? {
-                    eprintln!("Validating {}", infile.to_str().unwrap());
-                    let infile = indir.join(&infile);
-                    if let Err(e) = self.run_with_input(&cd, &outpath, &x.run, &infile) {
-                        // A hack to check if the subprocess exited with status code != 0.
-                        if let Error::IOError(ref inner) = e {
-                            if inner.kind() == std::io::ErrorKind::InvalidData {
-                                let inner = match e {
-                                    Error::IOError(inner) => inner,
-                                    _ => unreachable!(),
-                                };
-
-                                return Err(Error::ValidationFailed {
-                                    validator: val.path.clone(),
-                                    infile: infile.display().to_string(),
-                                    inner: Box::new(inner) as Box<dyn std::error::Error>,
-                                });
-                            }
-                        }
-                        return Err(e);
-                    }
+    }
+    if groups.len() <= 1 {
+        return false;
+    }
+    // Other outputs are compared with the output of the reference solution, if it is cross-checked.
+    let base = groups
+        .iter()
+        .position(|(_, sols)| sols.iter().any(|sol| sol.is_reference_solution))
+        .unwrap_or(0);
+    eprintln!("Solutions disagree on {}:", infile.display());
+    for (i, (output, sols)) in groups.iter().enumerate() {
+        let paths: Vec<&str> = sols.iter().map(|sol| sol.path.as_str()).collect();
+        match output {
+            Ok(stdout) => eprintln!(
+                "  {}: output {}",
+                paths.join(", "),
+                &sha256_hex(&[stdout])[..8]
+            ),
+            Err(verdict) => eprintln!("  {}: {:?}", paths.join(", "), verdict),
+        }
+        if let (Ok(expected), Ok(actual)) = (groups[base].0, output) {
+            if i != base {
+                if let Some(mismatch) = first_mismatch(expected, actual) {
+                    eprint!("  {}", mismatch);
                 }
-            } else {
-                eprintln!("warning");
-                let e = Error::ConfInvalid {
-                    description: format!("language not found: {}", val.language_name),
-                };
-                return Err(e);
             }
         }
+    }
+    if groups
+        .iter()
+        .any(|(_, sols)| sols.len() > groups[base].1.len())
+    {
+        eprintln!(
+            "  More solutions agree with each other than with {}; the expected output may be wrong",
+            groups[base].1[0].path
+        );
+    }
+    true
+}
 
-        Ok(())
+// Prints whether a solution behaved as expected after it ran on all testcases.
+// If it didn't, the error is saved in `error` unless another error was saved before.
+fn report_test_result(
+    proj_dir: &Path,
+    sol: &SolutionConfig,
+    overall_verdict: &Verdict,
+    options: &RunOptions,
+    error: &mut Option<Error>,
+) {
+    if options.is_testcase_filtered()
+        && *overall_verdict == Verdict::AC
+        && sol.expected_verdict != Verdict::AC
+    {
+        // The testcases that catch this solution may be filtered out.
+        eprintln!(
+            "Testing {} complete (result = AC, expected = {:?}; not caught by the selected testcases)",
+            proj_dir.join(&sol.path).display(),
+            sol.expected_verdict
+        );
+    } else if sol.expected_verdict != *overall_verdict {
+        eprintln!(
+            "Testing {} failed (result = {:?}, expected = {:?})",
+            proj_dir.join(&sol.path).display(),
+            overall_verdict,
+            sol.expected_verdict
+        );
+        error.get_or_insert(Error::VerdictMismatch {
+            expected: sol.expected_verdict.clone(),
+            actual: overall_verdict.clone(),
+        });
+    } else {
+        eprintln!(
+            "Testing {} complete (result = expected = {:?})",
+            proj_dir.join(&sol.path).display(),
+            sol.expected_verdict
+        )
     }
 }
 
-pub struct ProjectImpl;
+// Prints which testcases catch each solution whose expected verdict is not AC.
+// If a solution is caught by fewer than `min_kills` testcases, the error is saved in `error`
+// unless another error was saved before.
+// If `all_solutions` is true, testcases that catch none of them are also printed.
+fn report_kills(
+    wrong_solutions: &[(&SolutionConfig, &[&Path])],
+    testcases: &[PathBuf],
+    min_kills: usize,
+    all_solutions: bool,
+    error: &mut Option<Error>,
+) {
+    let names = |testcases: &mut dyn Iterator<Item = &Path>| {
+        testcases
+            .map(|testcase| testcase.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    for &(sol, kills) in wrong_solutions {
+        eprintln!(
+            "{} ({:?}) is caught by {} tests: {}",
+            sol.path,
+            sol.expected_verdict,
+            kills.len(),
+            names(&mut kills.iter().copied())
+        );
+        if kills.len() < min_kills {
+            eprintln!(
+                "{} is caught by too few tests (min_kills = {})",
+                sol.path, min_kills
+            );
+            error.get_or_insert(Error::NotEnoughKills {
+                path: sol.path.clone(),
+                kills: kills.len(),
+                min_kills,
+            });
+        }
+    }
+    if all_solutions && !wrong_solutions.is_empty() {
+        let mut useless = testcases
+            .iter()
+            .map(|testcase| testcase.as_path())
+            .filter(|&testcase| {
+                wrong_solutions
+                    .iter()
+                    .all(|(_, kills)| !kills.contains(&testcase))
+            });
+        let useless = names(&mut useless);
+        if !useless.is_empty() {
+            eprintln!("Tests that catch no wrong solution: {}", useless);
+        }
+    }
+}
 
-impl IoUtilExt for ProjectImpl {}
-impl RunUtilExt for ProjectImpl {}
-impl ProjectExt for ProjectImpl {}
+// Can the remaining testcases no longer change whether a solution behaves as expected?
+// Once the overall verdict is worse than expected, the solution fails whatever happens next.
+// Once a wrong solution gets its expected verdict, it is rejected as intended;
+// a worse verdict on a later testcase doesn't change that it is rejected.
+fn is_verdict_settled(expected: &Verdict, overall: &Verdict) -> bool {
+    overall > expected || (overall == expected && *expected != Verdict::AC)
+}
 
 // Check if there is at most one reference solution.
 fn check_reference_solution(config: &CreoConfig) -> Result<()> {
@@ -408,13 +2513,45 @@ fn check_reference_solution(config: &CreoConfig) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
+    use std::sync::Mutex;
 
     use super::*;
+    use crate::entity::options::DEFAULT_COUNTEREXAMPLE_DIR;
 
     // TODO: better testing, especially better mocking (such as obtaining multiple files' content by read_from_file)
+    #[derive(Default)]
     struct MockProject {
-        processed: Vec<(String, String)>,
+        // Input files solutions ran on
+        ran: Mutex<Vec<String>>,
+        // Files written by overwrite_file, except for records
+        written: Mutex<Vec<String>>,
+        // Files removed by remove_file
+        removed: Mutex<Vec<String>>,
+        // Appended to the config file
+        extra_config: String,
+        // Files with given content, or None if they don't exist.
+        // Files written by overwrite_file are added here, including records.
+        // Other files have the default content.
+        files: Mutex<BTreeMap<PathBuf, Option<Vec<u8>>>>,
+        // Content of directories. The invalid directory doesn't exist by default,
+        // and other directories contain a and b.
+        dirs: BTreeMap<PathBuf, Vec<PathBuf>>,
+        // Files that validators reject
+        rejected: Vec<PathBuf>,
+    }
+
+    impl MockProject {
+        fn with_file(self, path: &str, content: Option<&[u8]>) -> Self {
+            let content = content.map(|content| content.to_vec());
+            self.files.lock().unwrap().insert(path.into(), content);
+            self
+        }
     }
+
+    // What open_file_for_read returns for files without given content.
+    // read_from_file reads it as the config file, and read_bytes_from_file as "correct output\n".
+    const DEFAULT_CONTENT: &[u8] = b"don't care";
+
     impl IoUtil for MockProject {
         fn create_file_if_nonexistent(
             &mut self,
@@ -423,13 +2560,26 @@ mod tests {
         ) -> Result<Box<dyn std::io::Write>> {
             Ok(Box::new(vec![]))
         }
-        fn open_file_for_read(&self, _filepath: &Path) -> Result<Box<dyn std::io::Read>> {
-            Ok(Box::new(b"don't care" as &[u8]))
+        fn open_file_for_read(&self, filepath: &Path) -> Result<Box<dyn std::io::Read>> {
+            match self.files.lock().unwrap().get(filepath) {
+                Some(Some(content)) => Ok(Box::new(std::io::Cursor::new(content.clone()))),
+                Some(None) => Err(std::io::Error::from(std::io::ErrorKind::NotFound).into()),
+                // Nothing is cached unless a test writes it.
+                None if filepath.starts_with(CACHE_DIR) => {
+                    Err(std::io::Error::from(std::io::ErrorKind::NotFound).into())
+                }
+                None => Ok(Box::new(DEFAULT_CONTENT)),
+            }
         }
         fn open_file_for_write(&self, _filepath: &Path) -> Result<Box<dyn std::io::Write>> {
             Ok(Box::new(vec![]))
         }
-        fn read_from_file(&self, _file: &mut dyn std::io::Read) -> Result<String> {
+        fn read_from_file(&self, file: &mut dyn std::io::Read) -> Result<String> {
+            let mut content = String::new();
+            file.read_to_string(&mut content)?;
+            if content.as_bytes() != DEFAULT_CONTENT {
+                return Ok(content);
+            }
             Ok(r#"
 [[generators]]
 language_name = "C++"
@@ -450,26 +2600,59 @@ is_reference_solution = true
 path = "val.cpp"
 language_name = "C++"
 "#
-            .to_string())
+            .to_string()
+                + &self.extra_config)
         }
-        fn read_bytes_from_file(&self, _file: &mut dyn std::io::Read) -> Result<Vec<u8>> {
-            Ok(b"correct output".to_vec())
+        fn read_bytes_from_file(&self, file: &mut dyn std::io::Read) -> Result<Vec<u8>> {
+            let mut content = vec![];
+            file.read_to_end(&mut content)?;
+            if content != DEFAULT_CONTENT {
+                return Ok(content);
+            }
+            Ok(b"correct output\n".to_vec())
         }
         fn write_str_to_file(&self, _file: &mut dyn std::io::Write, _s: &str) -> Result<()> {
             Ok(())
         }
+        fn write_bytes_to_file(&self, _file: &mut dyn std::io::Write, _s: &[u8]) -> Result<()> {
+            Ok(())
+        }
         fn mkdir_p(&mut self, _path: &Path) -> Result<()> {
             Ok(())
         }
         fn to_absolute(&self, _path: &Path) -> Result<PathBuf> {
             Ok("gen-absolute.cpp".into())
         }
-        fn list_dir(&self, _path: &Path) -> Result<Vec<PathBuf>> {
-            Ok(vec!["a".into(), "b".into()])
+        fn file_size(&self, path: &Path) -> Result<u64> {
+            let mut file = self.open_file_for_read(path)?;
+            Ok(self.read_bytes_from_file(&mut file)?.len() as u64)
+        }
+        fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+            match self.dirs.get(path) {
+                Some(files) => Ok(files.clone()),
+                None if path.ends_with("invalid") => {
+                    Err(std::io::Error::from(std::io::ErrorKind::NotFound).into())
+                }
+                None => Ok(vec!["a".into(), "b".into()]),
+            }
         }
         fn remove_dir_all(&self, _path: &Path) -> Result<()> {
             Ok(())
         }
+        fn remove_file(&self, path: &Path) -> Result<()> {
+            let path = path.to_str().unwrap().to_owned();
+            self.removed.lock().unwrap().push(path);
+            Ok(())
+        }
+        fn overwrite_file(&self, filepath: &Path, content: &[u8]) -> Result<()> {
+            let mut files = self.files.lock().unwrap();
+            files.insert(filepath.to_owned(), Some(content.to_vec()));
+            if !filepath.starts_with(CACHE_DIR) {
+                let filepath = filepath.to_str().unwrap().to_owned();
+                self.written.lock().unwrap().push(filepath);
+            }
+            Ok(())
+        }
     }
     impl RunUtil for MockProject {
         fn compile(&mut self, _cd: &Path, src: &Path, _compile: &[String]) -> Result<PathBuf> {
@@ -485,26 +2668,33 @@ language_name = "C++"
             _cd: &Path,
             _exec: &Path,
             _run: &[String],
-            _infile: &Path,
+            infile: &Path,
         ) -> Result<Vec<u8>> {
-            Ok((b"wrong output" as &[u8]).to_owned())
+            if self.rejected.iter().any(|rejected| rejected == infile) {
+                let e = std::io::Error::new(std::io::ErrorKind::InvalidData, "rejected");
+                return Err(e.into());
+            }
+            Ok((b"wrong output\n" as &[u8]).to_owned())
         }
-        fn run_pipe(
-            &mut self,
+        fn build_command(&self, _run: &[String], _infile: &Path, _outfile: &Path) -> Vec<String> {
+            vec!["gcc".to_owned()]
+        }
+        fn run_with_limits(
+            &self,
             _cd: &Path,
-            _exec: &Path,
+            exec: &Path,
             _run: &[String],
             infile: &Path,
-            outfile: &Path,
-        ) -> Result<()> {
-            self.processed.push((
-                infile.to_str().unwrap().to_owned(),
-                outfile.to_str().unwrap().to_owned(),
-            ));
-            Ok(())
-        }
-        fn build_command(&self, _run: &[String], _infile: &Path, _outfile: &Path) -> Vec<String> {
-            vec!["gcc".to_owned()]
+            _limits: &Limits,
+        ) -> Result<RunResult> {
+            assert_eq!(exec, PathBuf::from("outpath"));
+            let infile = infile.to_str().unwrap().to_owned();
+            self.ran.lock().unwrap().push(infile);
+            Ok(RunResult {
+                stdout: b"wrong output\n".to_vec(),
+                exit_code: Some(0),
+                ..Default::default()
+            })
         }
     }
     impl ProjectExt for MockProject {}
@@ -518,6 +2708,7 @@ language_name = "C++"
                 language_name: "".to_owned(),
                 expected_verdict: Verdict::AC,
                 is_reference_solution: true,
+                timing_sensitive: false,
             }],
             ..Default::default()
         };
@@ -534,6 +2725,7 @@ language_name = "C++"
                     language_name: "".to_owned(),
                     expected_verdict: Verdict::AC,
                     is_reference_solution: true,
+                    timing_sensitive: false,
                 };
                 2
             ],
@@ -556,6 +2748,7 @@ language_name = "C++"
                 language_name: "".to_owned(),
                 expected_verdict: Verdict::WA,
                 is_reference_solution: true,
+                timing_sensitive: false,
             }],
             ..Default::default()
         };
@@ -569,36 +2762,180 @@ language_name = "C++"
         assert!(desc.contains("AC"), "desc = {}", desc);
     }
 
+    #[test]
+    fn report_kills_works() {
+        let wa = SolutionConfig {
+            path: "wa.cpp".to_owned(),
+            language_name: "C++".to_owned(),
+            expected_verdict: Verdict::WA,
+            is_reference_solution: false,
+            timing_sensitive: false,
+        };
+        let testcases: Vec<PathBuf> = vec!["1.txt".into(), "2.txt".into()];
+        let kills = [testcases[1].as_path()];
+        let mut error = None;
+        report_kills(&[(&wa, &kills)], &testcases, 1, true, &mut error);
+        assert!(error.is_none());
+        report_kills(&[(&wa, &kills)], &testcases, 2, true, &mut error);
+        assert!(matches!(
+            error,
+            Some(Error::NotEnoughKills {
+                kills: 1,
+                min_kills: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn failure_verdict_works() {
+        let limits = Limits {
+            time_limit: 2.0,
+            memory_limit: 1,
+            cpu: None,
+        };
+        let ok = RunResult {
+            exit_code: Some(0),
+            max_rss: Some(512),
+            ..Default::default()
+        };
+        assert_eq!(failure_verdict(&ok, &limits), None);
+        let re = RunResult {
+            exit_code: Some(1),
+            ..ok.clone()
+        };
+        assert_eq!(failure_verdict(&re, &limits), Some(Verdict::RE));
+        let mle = RunResult {
+            signal: Some(9),
+            exit_code: None,
+            max_rss: Some(4096),
+            ..ok.clone()
+        };
+        assert_eq!(failure_verdict(&mle, &limits), Some(Verdict::MLE));
+        // A timeout takes precedence over anything else.
+        let tle = RunResult {
+            timed_out: true,
+            ..mle
+        };
+        assert_eq!(failure_verdict(&tle, &limits), Some(Verdict::TLE));
+    }
+
+    #[test]
+    fn is_verdict_settled_works() {
+        // An AC solution has to be run on every testcase.
+        assert!(!is_verdict_settled(&Verdict::AC, &Verdict::AC));
+        assert!(is_verdict_settled(&Verdict::AC, &Verdict::WA));
+        assert!(is_verdict_settled(&Verdict::TLE, &Verdict::TLE));
+        assert!(!is_verdict_settled(&Verdict::TLE, &Verdict::AC));
+        assert!(is_verdict_settled(&Verdict::WA, &Verdict::RE));
+    }
+
     #[test]
     fn add_project_works() {
-        let mut project = MockProject { processed: vec![] };
+        let mut project = MockProject::default();
         project.add(".", "val", "test.cpp").unwrap();
     }
 
     #[test]
     fn gen_project_works() {
-        let mut project = MockProject { processed: vec![] };
+        let mut project = MockProject::default();
         project.gen(".").unwrap();
     }
 
     #[test]
     fn refgen_project_works() {
-        let mut project = MockProject { processed: vec![] };
-        project.refgen(".").unwrap();
-        assert_eq!(
-            project.processed,
-            vec![
-                ("./in/a".to_owned(), "./out/a".to_owned()),
-                ("./in/b".to_owned(), "./out/b".to_owned()),
-            ]
-        );
+        let mut project = MockProject::default();
+        project.refgen(".", &RunOptions::default()).unwrap();
+        assert_eq!(*project.ran.lock().unwrap(), vec!["./in/a", "./in/b"]);
+        assert_eq!(*project.written.lock().unwrap(), vec!["./out/a", "./out/b"]);
+
+        let mut project = MockProject::default();
+        let options = RunOptions {
+            jobs: 2,
+            ..Default::default()
+        };
+        project.refgen(".", &options).unwrap();
+        let mut written = project.written.into_inner().unwrap();
+        written.sort();
+        assert_eq!(written, vec!["./out/a", "./out/b"]);
+    }
+
+    #[test]
+    fn refgen_project_removes_outputs_rejected_by_output_validators() {
+        let mut project = MockProject {
+            extra_config: r#"
+[[output_validators]]
+path = "oval.cpp"
+language_name = "C++"
+"#
+            .to_owned(),
+            rejected: vec!["./out/b".into()],
+            ..Default::default()
+        };
+        let result = project.refgen(".", &RunOptions::default());
+        if let Err(Error::ValidationFailed {
+            validator, infile, ..
+        }) = result
+        {
+            assert_eq!(validator, "oval.cpp");
+            assert_eq!(infile, "./out/b");
+        } else {
+            unreachable!("unreachable: the assertion above does not hold");
+        }
+        assert_eq!(*project.removed.lock().unwrap(), vec!["./out/b"]);
+
+        // Outputs that pass are kept.
+        let mut project = MockProject {
+            rejected: vec![],
+            ..project
+        };
+        project.removed.lock().unwrap().clear();
+        project.refgen(".", &RunOptions::default()).unwrap();
+        assert!(project.removed.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn refgen_project_filters_testcases() {
+        let mut project = MockProject::default();
+        let options = RunOptions {
+            testcase: Some(glob::Pattern::new("b").unwrap()),
+            ..Default::default()
+        };
+        project.refgen(".", &options).unwrap();
+        assert_eq!(*project.ran.lock().unwrap(), vec!["./in/b"]);
+        assert_eq!(*project.written.lock().unwrap(), vec!["./out/b"]);
+
+        // The reference solution doesn't match, so nothing could be generated
+        let mut project = MockProject::default();
+        let options = RunOptions {
+            solution: Some(glob::Pattern::new("wa.cpp").unwrap()),
+            ..Default::default()
+        };
+        match project.refgen(".", &options) {
+            Err(Error::ReferenceSolutionFiltered { pattern, reference }) => {
+                assert_eq!(
+                    (pattern.as_str(), reference.as_str()),
+                    ("wa.cpp", "sol.cpp")
+                );
+            }
+            other => panic!("{:?}", other.map_err(|e| e.to_string())),
+        }
+        assert!(project.ran.lock().unwrap().is_empty());
+
+        // The reference solution matches
+        let options = RunOptions {
+            solution: Some(glob::Pattern::new("sol*").unwrap()),
+            ..Default::default()
+        };
+        project.refgen(".", &options).unwrap();
+        assert_eq!(*project.ran.lock().unwrap(), vec!["./in/a", "./in/b"]);
     }
 
     #[test]
     fn test_project_works() {
-        let mut project = MockProject { processed: vec![] };
+        let mut project = MockProject::default();
         // TODO: explain why RE != AC is returned
-        let result = project.test(".");
+        let result = project.test(".", &RunOptions::default());
         // We use a pattern matching because Error can't implement PartialEq
         // (because of std::io::Error, which doesn't implement PartialEq)
         if let Err(Error::VerdictMismatch { expected, actual }) = result {
@@ -609,10 +2946,426 @@ language_name = "C++"
         }
     }
 
+    #[test]
+    fn test_project_works_in_parallel() {
+        let mut project = MockProject::default();
+        let options = RunOptions {
+            cpus: vec![0, 1],
+            ..Default::default()
+        };
+        let result = project.test(".", &options);
+        assert!(matches!(
+            result,
+            Err(Error::VerdictMismatch {
+                expected: Verdict::AC,
+                actual: Verdict::WA,
+            })
+        ));
+        assert_eq!(project.ran.into_inner().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn show_diff_project_works() {
+        let mut project = MockProject::default();
+        project.show_diff(".", "sol.cpp", "a").unwrap();
+        let result = project.show_diff(".", "nonexistent.cpp", "a");
+        assert!(matches!(result, Err(Error::ConfInvalid { .. })));
+    }
+
+    #[test]
+    fn stress_project_works() {
+        let mut project = MockProject::default();
+        let options = StressOptions {
+            generator: vec!["gen.cpp".to_owned(), "10".to_owned()],
+            solutions: vec!["sol.cpp".to_owned()],
+            seed: 1,
+            iterations: Some(3),
+            counterexample_dir: DEFAULT_COUNTEREXAMPLE_DIR.to_owned(),
+        };
+        project.stress(".", &options).unwrap();
+        // Each run generates an input and runs two solutions on it.
+        assert_eq!(project.ran.lock().unwrap().len(), 9);
+        assert!(project.written.lock().unwrap().is_empty());
+
+        let options = StressOptions {
+            generator: vec!["nonexistent.py".to_owned()],
+            ..options
+        };
+        let result = project.stress(".", &options);
+        assert!(matches!(result, Err(Error::ConfInvalid { .. })));
+    }
+
+    #[test]
+    fn minimize_project_works() {
+        // The mock solutions always print the same output, so there is nothing to minimize.
+        let mut project = MockProject::default();
+        project
+            .minimize(".", "sol.cpp", "a", DEFAULT_COUNTEREXAMPLE_DIR)
+            .unwrap();
+        assert!(project.written.lock().unwrap().is_empty());
+
+        let result = project.minimize(".", "nonexistent.cpp", "a", DEFAULT_COUNTEREXAMPLE_DIR);
+        assert!(matches!(result, Err(Error::ConfInvalid { .. })));
+    }
+
+    #[test]
+    fn coverage_project_needs_coverage_config() {
+        let mut project = MockProject::default();
+        let result = project.coverage(".");
+        assert!(matches!(result, Err(Error::ConfInvalid { .. })));
+    }
+
     #[test]
     fn val_project_works() {
-        let mut project = MockProject { processed: vec![] };
-        let result = project.val(".");
+        let mut project = MockProject::default();
+        let result = project.val(".", &RunOptions::default());
         result.unwrap();
+
+        let options = RunOptions {
+            fuzz: true,
+            ..Default::default()
+        };
+        project.val(".", &options).unwrap();
+        assert!(project.removed.lock().unwrap().is_empty());
+
+        // Every mock file has the same content, so all inputs but the first are duplicates.
+        let options = RunOptions {
+            remove_duplicates: true,
+            ..Default::default()
+        };
+        project.val(".", &options).unwrap();
+        assert_eq!(*project.removed.lock().unwrap(), vec!["./in/b"]);
+    }
+
+    #[test]
+    fn val_project_fuzzes_non_utf8_inputs() {
+        let mut project = MockProject::default()
+            .with_file("./in/a", Some(b"1 \xff\n"))
+            .with_file("./in/b", Some(b"2 3\n"));
+        let options = RunOptions {
+            fuzz: true,
+            ..Default::default()
+        };
+        project.val(".", &options).unwrap();
+    }
+
+    #[test]
+    fn val_project_checks_invalid_dir() {
+        // The validator accepts the invalid input.
+        let mut project = MockProject {
+            dirs: BTreeMap::from([("./invalid".into(), vec!["bad".into()])]),
+            ..Default::default()
+        };
+        let result = project.val(".", &RunOptions::default());
+        if let Err(Error::InvalidInputAccepted { validator, infile }) = result {
+            assert_eq!(validator, "val.cpp");
+            assert_eq!(infile, "./invalid/bad");
+        } else {
+            unreachable!("unreachable: the assertion above does not hold");
+        }
+
+        // The validator rejects the invalid input.
+        let mut project = MockProject {
+            dirs: BTreeMap::from([("./invalid".into(), vec!["bad".into()])]),
+            rejected: vec!["./invalid/bad".into()],
+            ..Default::default()
+        };
+        project.val(".", &RunOptions::default()).unwrap();
+    }
+
+    #[test]
+    fn report_bounds_hit_checks_required_bounds() {
+        let mut val = ValidatorConfig {
+            path: "val.cpp".to_owned(),
+            language_name: "C++".to_owned(),
+            overview_log: true,
+            required_bounds: BTreeMap::new(),
+        };
+        let mut bounds_hit = BTreeMap::new();
+        bounds_hit.insert(
+            "n".to_owned(),
+            BoundsHit {
+                min: true,
+                max: false,
+            },
+        );
+        report_bounds_hit(&val, &bounds_hit).unwrap();
+        val.required_bounds.insert("n".to_owned(), vec![Bound::Min]);
+        report_bounds_hit(&val, &bounds_hit).unwrap();
+        val.required_bounds.insert("n".to_owned(), vec![Bound::Max]);
+        let result = report_bounds_hit(&val, &bounds_hit);
+        assert!(matches!(
+            result,
+            Err(Error::BoundNotHit {
+                bound: Bound::Max,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn cross_check_project_needs_two_solutions() {
+        let mut project = MockProject::default();
+        let result = project.cross_check(".", &RunOptions::default());
+        assert!(matches!(result, Err(Error::ConfInvalid { .. })));
+        assert!(project.ran.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn report_disagreement_works() {
+        let solution = |path: &str, is_reference_solution| SolutionConfig {
+            path: path.to_owned(),
+            language_name: "C++".to_owned(),
+            expected_verdict: Verdict::AC,
+            is_reference_solution,
+            timing_sensitive: false,
+        };
+        let (sol, alt, tle) = (
+            solution("sol.cpp", true),
+            solution("alt.cpp", false),
+            solution("tle.cpp", false),
+        );
+        let solutions = [&sol, &alt, &tle];
+        let infile = Path::new("1.txt");
+        let same = [
+            Ok(b"1\n".to_vec()),
+            Ok(b"1\n".to_vec()),
+            Ok(b"1\n".to_vec()),
+        ];
+        assert!(!report_disagreement(infile, &solutions, &same));
+        let different = [Ok(b"1\n".to_vec()), Ok(b"2\n".to_vec()), Err(Verdict::TLE)];
+        assert!(report_disagreement(infile, &solutions, &different));
+    }
+
+    #[test]
+    fn stats_project_works() {
+        let mut project = MockProject {
+            extra_config: "[judge_limits]\nmax_tests = 1\nmax_total_input_size = 1.0\n".to_owned(),
+            ..Default::default()
+        }
+        .with_file("./in/a", Some(b"1 2\n"))
+        .with_file("./out/b", None);
+        let (indir, sol) = (Path::new("./in"), Path::new("./sol.cpp"));
+        let key = project.time_key(sol, &indir.join("a")).unwrap();
+        project.write_record(&key, "0.5").unwrap();
+        let key = project.origin_key(indir, Path::new("a")).unwrap();
+        project.write_record(&key, "gen.cpp").unwrap();
+        project.stats(".").unwrap();
+        assert!(project.ran.lock().unwrap().is_empty());
+        assert!(project.written.lock().unwrap().is_empty());
+
+        let config = project.read_config(Path::new(".")).unwrap();
+        let (total, by_generator) = project.testset_stats(Path::new("."), &config).unwrap();
+        assert_eq!(total.tests, 2);
+        // b's input and a's output have the default content "correct output\n".
+        assert_eq!(total.input_size, 4 + 15);
+        assert_eq!(total.output_size, 15);
+        assert_eq!(total.time, 0.5);
+        assert_eq!(total.untimed, 1);
+        let generators: Vec<(&str, usize)> = by_generator
+            .iter()
+            .map(|(generator, stats)| (generator.as_str(), stats.tests))
+            .collect();
+        assert_eq!(generators, vec![("(unknown)", 1), ("gen.cpp", 1)]);
+        assert_eq!(by_generator["gen.cpp"].input_size, 4);
+
+        let warnings = judge_limit_warnings(&total, &config.judge_limits);
+        assert_eq!(
+            warnings,
+            vec!["the number of tests (2) exceeds max_tests = 1"]
+        );
+    }
+
+    #[test]
+    fn constants_project_writes_changed_sources_next_to_every_source() {
+        let mut project = MockProject {
+            extra_config: r#"
+[[solutions]]
+path = "sols/wa.cpp"
+language_name = "C++"
+expected_verdict = "wa"
+
+[constants]
+N_MAX = 5
+"#
+            .to_owned(),
+            ..Default::default()
+        };
+        for name in [
+            constants::CPP_HEADER,
+            constants::PYTHON_MODULE,
+            constants::RUST_FILE,
+        ] {
+            project = project.with_file(&format!("./{}", name), None);
+        }
+        // Files generated for old constants are rewritten.
+        project = project
+            .with_file(
+                "sols/constants.h",
+                Some(b"// Generated by creo from [constants] in creo.toml. Do not edit.\n"),
+            )
+            .with_file("sols/constants.py", None)
+            .with_file("sols/constants.rs", None);
+        project.constants(".", None).unwrap();
+        let written = project.written.lock().unwrap().clone();
+        assert_eq!(
+            written,
+            vec![
+                "./constants.h",
+                "./constants.py",
+                "./constants.rs",
+                "sols/constants.h",
+                "sols/constants.py",
+                "sols/constants.rs",
+            ]
+        );
+        let files = project.files.lock().unwrap().clone();
+        let header = files[Path::new("sols/constants.h")].clone().unwrap();
+        assert!(String::from_utf8(header)
+            .unwrap()
+            .contains("const long long N_MAX = 5;\n"));
+
+        // Unchanged files are not rewritten, so that sources are not compiled again.
+        project.constants(".", None).unwrap();
+        assert_eq!(project.written.lock().unwrap().len(), written.len());
+    }
+
+    #[test]
+    fn constants_project_keeps_files_not_generated_by_creo() {
+        let header: &[u8] = b"const int N_MAX = 10;\n";
+        let mut project = MockProject {
+            extra_config: "[constants]\nN_MAX = 5\n".to_owned(),
+            ..Default::default()
+        }
+        .with_file("./constants.h", Some(header));
+        match project.constants(".", None) {
+            Err(Error::NotGeneratedByCreo { path }) => assert_eq!(path, "./constants.h"),
+            other => panic!("{:?}", other.map_err(|e| e.to_string())),
+        }
+        assert!(project.written.lock().unwrap().is_empty());
+        let files = project.files.lock().unwrap();
+        assert_eq!(files[Path::new("./constants.h")].as_deref(), Some(header));
+    }
+
+    #[test]
+    fn statement_project_works() {
+        let mut project = MockProject {
+            extra_config: "[constants]\nN_MAX = 5\n".to_owned(),
+            dirs: BTreeMap::from([
+                ("./task".into(), vec!["en.md".into(), "notes.txt".into()]),
+                ("./in".into(), vec!["1.txt".into(), "sample-1.txt".into()]),
+            ]),
+            ..Default::default()
+        }
+        .with_file(
+            "./task/en.md",
+            Some(b"# Problem\nN is at most {{N_MAX}}.\n"),
+        )
+        .with_file("./in/sample-1.txt", Some(b"1 2\n"))
+        .with_file("./out/sample-1.txt", Some(b"3\n"));
+        project.statement(".").unwrap();
+        assert_eq!(*project.written.lock().unwrap(), vec!["./task/en.html"]);
+        let files = project.files.lock().unwrap();
+        let html = files[Path::new("./task/en.html")].clone().unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert!(html.contains("<p>N is at most 5.</p>"));
+        assert!(html.contains("<pre>1 2\n</pre>"));
+        assert!(html.contains("<pre>3\n</pre>"));
+    }
+
+    #[test]
+    fn check_samples_project_works() {
+        let project = || {
+            let mut project = MockProject {
+                dirs: BTreeMap::from([
+                    ("./task".into(), vec!["en.md".into()]),
+                    ("./in".into(), vec!["1.txt".into(), "sample-1.txt".into()]),
+                ]),
+                ..Default::default()
+            }
+            .with_file("./in/sample-1.txt", Some(b"1 2\n"))
+            .with_file("./out/sample-1.txt", Some(b"wrong output\n"));
+            // save_to_cache writes through create_file_if_nonexistent, which the mock discards.
+            for content in ["1 2\n", "wrong output\n", "3\n", "4 5\n"] {
+                let path = Path::new(CACHE_DIR)
+                    .join("sample")
+                    .join(sha256_hex(&[content.as_bytes()]));
+                project = project.with_file(path.to_str().unwrap(), Some(content.as_bytes()));
+            }
+            project
+        };
+
+        // The mock reference solution prints "wrong output\n", which is the sample output.
+        let mut matching = project().with_file(
+            "./task/en.md",
+            Some(b"```sample-input\n1 2\n```\n```sample-output\nwrong output\n```\n"),
+        );
+        matching.check_samples(".").unwrap();
+        assert_eq!(matching.ran.lock().unwrap().len(), 1);
+
+        // One input too many, an output that differs from the testcase,
+        // and a reference solution that gets WA on it.
+        let mut mismatching = project().with_file(
+            "./task/en.md",
+            Some(
+                b"```sample-input\n1 2\n```\n```sample-output\n3\n```\n```sample-input\n4 5\n```\n",
+            ),
+        );
+        match mismatching.check_samples(".") {
+            Err(Error::SampleMismatch { count }) => assert_eq!(count, 3),
+            other => panic!("{:?}", other.map_err(|e| e.to_string())),
+        }
+    }
+
+    #[test]
+    fn check_translations_project_works() {
+        let accepted_file = Path::new("./task/translations.toml");
+        let mut project = MockProject {
+            dirs: BTreeMap::from([("./task".into(), vec!["en.md".into(), "ja.md".into()])]),
+            ..Default::default()
+        }
+        .with_file("./task/en.md", Some(b"# Problem\n$N \\leq 5$\n"))
+        .with_file("./task/ja.md", Some(b"# Mondai\n$N \\leq 5$\n"))
+        .with_file(accepted_file.to_str().unwrap(), None);
+        let accepted = |project: &MockProject| -> BTreeMap<String, String> {
+            let files = project.files.lock().unwrap();
+            match &files[accepted_file] {
+                Some(content) => toml::from_str(std::str::from_utf8(content).unwrap()).unwrap(),
+                None => BTreeMap::new(),
+            }
+        };
+        let hash = |project: &MockProject, name: &str| {
+            let path = Path::new("./task").join(name);
+            project.hash_file_if_exists(&path).unwrap().unwrap()
+        };
+
+        // Statements that have never been accepted are only recorded with --accept.
+        project.check_translations(".", false).unwrap();
+        assert!(project.written.lock().unwrap().is_empty());
+        project.check_translations(".", true).unwrap();
+        assert_eq!(
+            accepted(&project),
+            BTreeMap::from([
+                ("en.md".to_owned(), hash(&project, "en.md")),
+                ("ja.md".to_owned(), hash(&project, "ja.md")),
+            ])
+        );
+
+        // Only en.md changes: its new hash is not recorded until the change is accepted.
+        let old = accepted(&project);
+        project = project.with_file("./task/en.md", Some(b"# Problem\nWe have $N \\leq 5$.\n"));
+        project.check_translations(".", false).unwrap();
+        assert_eq!(accepted(&project), old);
+        project.check_translations(".", true).unwrap();
+        assert_eq!(accepted(&project)["en.md"], hash(&project, "en.md"));
+
+        // Statements with different numbers are an error, and nothing is recorded.
+        let old = accepted(&project);
+        project = project.with_file("./task/ja.md", Some(b"# Mondai\n$N \\leq 6$\n"));
+        match project.check_translations(".", true) {
+            Err(Error::TranslationsDiffer { count }) => assert!(count > 0),
+            other => panic!("{:?}", other.map_err(|e| e.to_string())),
+        }
+        assert_eq!(accepted(&project), old);
     }
 }
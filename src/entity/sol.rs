@@ -14,6 +14,11 @@ pub struct SolutionConfig {
     #[serde(default)]
     #[serde(skip_serializing_if = "is_false")]
     pub is_reference_solution: bool,
+    /// Is this solution too close to the time limit to share the machine with other runs?
+    /// If true, `creo test` runs it on one testcase at a time, unless CPUs are given by --cpus.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub timing_sensitive: bool,
 }
 
 /// Judge's verdict. Bigger it is, worse it is.
@@ -72,6 +77,7 @@ mod tests {
             language_name: "C++".to_owned(),
             expected_verdict: Verdict::AC,
             is_reference_solution: false,
+            timing_sensitive: false,
         })
         .unwrap();
         // expected_verdict is skipped because it is AC.
@@ -85,6 +91,7 @@ language_name = "C++"
             language_name: "Rust".to_owned(),
             expected_verdict: Verdict::WA,
             is_reference_solution: true,
+            timing_sensitive: false,
         })
         .unwrap();
         // expected_verdict is serialized because it is not AC.
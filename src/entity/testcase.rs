@@ -9,6 +9,13 @@ pub struct TestcaseConfig {
     /// Where output files are located. Before generation, files in this directory will be DELETED.
     #[serde(default = "outdir_default")]
     pub outdir: String,
+    /// Where inputs known to be invalid are located. Every validator must reject every file in this directory.
+    /// It is fine if this directory doesn't exist.
+    #[serde(default = "invalid_dir_default")]
+    pub invalid_dir: String,
+    /// Testcases shown in statements as samples, by a glob pattern on their names. Samples are shown in order of name.
+    #[serde(default = "samples_default")]
+    pub samples: String,
 }
 
 fn indir_default() -> String {
@@ -19,11 +26,21 @@ fn outdir_default() -> String {
     "out".to_owned()
 }
 
+fn invalid_dir_default() -> String {
+    "invalid".to_owned()
+}
+
+fn samples_default() -> String {
+    "*sample*".to_owned()
+}
+
 impl Default for TestcaseConfig {
     fn default() -> Self {
         Self {
             indir: indir_default(),
             outdir: outdir_default(),
+            invalid_dir: invalid_dir_default(),
+            samples: samples_default(),
         }
     }
 }
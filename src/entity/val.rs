@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use std::collections::BTreeMap;
+use std::fmt;
+
 /// Configuration for validator files.
 #[derive(Serialize, Deserialize, Clone, PartialEq, PartialOrd, Debug)]
 pub struct ValidatorConfig {
@@ -7,4 +10,46 @@ pub struct ValidatorConfig {
     pub path: String,
     /// In which language is this validator written?
     pub language_name: String,
+    /// Does this validator write a testlib test overview log?
+    /// If so, `creo val` passes `--testOverviewLogFileName` to it and reports bounds no input reaches.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub overview_log: bool,
+    /// Bounds that some input must reach, e.g. `{ n = ["max"] }`.
+    /// Keys are variable names as written in the test overview log.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub required_bounds: BTreeMap<String, Vec<Bound>>,
+}
+
+/// Configuration for output validator files.
+/// An output validator reads an output from the standard input, and gets the path to its input as the first argument.
+/// It should exit with a non-zero status if the output is malformed.
+#[derive(Serialize, Deserialize, Clone, PartialEq, PartialOrd, Debug)]
+pub struct OutputValidatorConfig {
+    /// Path to the output validator file.
+    pub path: String,
+    /// In which language is this output validator written?
+    pub language_name: String,
+}
+
+fn is_false(x: &bool) -> bool {
+    !x
+}
+
+/// A bound of a variable read by a validator.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Bound {
+    Min,
+    Max,
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bound::Min => write!(f, "minimum"),
+            Bound::Max => write!(f, "maximum"),
+        }
+    }
 }
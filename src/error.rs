@@ -1,4 +1,5 @@
 use crate::entity::sol::Verdict;
+use crate::entity::val::Bound;
 
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -25,13 +26,49 @@ pub enum Error {
         #[source]
         toml::de::Error,
     ),
+    #[error("Validator accepted an invalid input: validator = {validator}, infile = {infile}")]
+    InvalidInputAccepted { validator: String, infile: String },
+    #[error("No input has {variable} at its {bound} (validator = {validator})")]
+    BoundNotHit {
+        validator: String,
+        variable: String,
+        bound: Bound,
+    },
+    #[error("Testcases have {count} formatting problems")]
+    BadFormat { count: usize },
+    #[error("Running {path} on {infile} failed: {verdict:?}")]
+    RunFailed {
+        path: String,
+        infile: String,
+        verdict: Verdict,
+    },
+    #[error("{path} is caught by {kills} tests, fewer than min_kills = {min_kills}")]
+    NotEnoughKills {
+        path: String,
+        kills: usize,
+        min_kills: usize,
+    },
+    #[error("Samples in statements have {count} mismatches")]
+    SampleMismatch { count: usize },
+    #[error("Sample {testcase} has no output. Run `creo refgen` first")]
+    SampleOutputMissing { testcase: String },
+    #[error("Solutions expected to be AC disagree on {count} testcases")]
+    SolutionsDisagree { count: usize },
+    #[error("--solution {pattern} doesn't match the reference solution {reference}, the only solution refgen runs")]
+    ReferenceSolutionFiltered { pattern: String, reference: String },
+    #[error("{file} uses undefined constants: {names}")]
+    UndefinedConstants { file: String, names: String },
+    #[error("{path} was not generated by creo: rename it, so that constants can be written there")]
+    NotGeneratedByCreo { path: String },
+    #[error("Translations of the statement have {count} differences")]
+    TranslationsDiffer { count: usize },
     #[error("Unknown entity type: {entity_type}")]
     UnknownEntityType { entity_type: String },
     #[error("Validation failed: validator = {validator}, infile = {infile}")]
     ValidationFailed {
         validator: String,
         infile: String,
-        inner: Box<dyn std::error::Error + 'static>,
+        inner: Box<dyn std::error::Error + Send + Sync + 'static>,
     },
 }
 
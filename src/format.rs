@@ -0,0 +1,726 @@
+use std::collections::BTreeMap;
+
+use crate::entity::gen::{Distribution, SizePreset};
+
+/// A declarative description of an input format.
+///
+/// Each line of the description describes one line of the input, as comma-separated declarations:
+/// - `N: int in [1, 2e5]`: an integer in a range
+/// - `A: int[N] in [0, 1e9]`: `N` space-separated integers
+/// - `S: string of [a-z] len [1, N]`: a string of the given characters, whose length is in a range
+///
+/// A line prefixed with `repeat M:` describes `M` lines of the same form.
+/// Bounds, lengths and repeat counts are expressions of integers (e.g. `2e5`), `+`, `-`, `*`
+/// and integers declared earlier outside arrays. Integers on a repeated line can be used only later on the same line.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InputFormat {
+    lines: Vec<LineSpec>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct LineSpec {
+    /// How many times this line is repeated. None if it appears once.
+    repeat: Option<Expr>,
+    decls: Vec<Decl>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Decl {
+    name: String,
+    /// Number of tokens, if this is an array.
+    len: Option<Expr>,
+    kind: Kind,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Kind {
+    Int {
+        min: Expr,
+        max: Expr,
+    },
+    String {
+        /// Inclusive ranges of allowed characters.
+        chars: Vec<(char, char)>,
+        min_len: Expr,
+        max_len: Expr,
+    },
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Expr {
+    Num(i128),
+    Var(String),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+}
+
+/// Where and why an input doesn't match its format.
+#[derive(Clone, PartialEq, Eq, Debug, thiserror::Error)]
+#[error("line {line}, column {column}: {message}")]
+pub struct FormatError {
+    /// 1-indexed line number.
+    pub line: usize,
+    /// 1-indexed column number.
+    pub column: usize,
+    pub message: String,
+}
+
+impl InputFormat {
+    /// Parses a description of an input format, one string per line.
+    /// Returns a description of the first problem if it is malformed.
+    pub fn parse(lines: &[String]) -> Result<InputFormat, String> {
+        let mut scalars = vec![];
+        let mut specs = vec![];
+        for (i, line) in lines.iter().enumerate() {
+            let spec = parse_line(line, &mut scalars)
+                .map_err(|e| format!("input_format line {} (`{}`): {}", i + 1, line, e))?;
+            specs.push(spec);
+        }
+        Ok(InputFormat { lines: specs })
+    }
+
+    /// Checks that an input follows this format exactly, including single spaces between tokens
+    /// and a newline at the end of each line.
+    pub fn validate(&self, input: &str) -> Result<(), FormatError> {
+        let error = |line: usize, column: usize, message: String| FormatError {
+            line,
+            column,
+            message,
+        };
+        let Some(content) = input.strip_suffix('\n') else {
+            let line = input.split('\n').count();
+            let column = input.rsplit('\n').next().map_or(0, |x| x.len()) + 1;
+            return Err(error(line, column, "missing final newline".to_owned()));
+        };
+        let lines: Vec<&str> = content.split('\n').collect();
+        let mut env = BTreeMap::new();
+        let mut index = 0;
+        for spec in &self.lines {
+            let count = match &spec.repeat {
+                Some(repeat) => eval(repeat, &env),
+                None => 1,
+            };
+            for _ in 0..count {
+                let Some(line) = lines.get(index) else {
+                    return Err(error(
+                        index + 1,
+                        1,
+                        format!("expected {}, found the end of the input", names(spec)),
+                    ));
+                };
+                let mut line_env = env.clone();
+                validate_line(spec, line, &mut line_env)
+                    .map_err(|(column, message)| error(index + 1, column, message))?;
+                if spec.repeat.is_none() {
+                    env = line_env;
+                }
+                index += 1;
+            }
+        }
+        if index < lines.len() {
+            return Err(error(index + 1, 1, "extra line".to_owned()));
+        }
+        Ok(())
+    }
+}
+
+impl InputFormat {
+    /// Generates a random input that follows this format.
+    /// `size` decides integers outside arrays and repeated lines (such as N) and lengths of strings,
+    /// and `distributions` decides how other values are drawn for each variable (uniform by default).
+    /// Returns a description of the problem if some range is empty.
+    pub fn generate(
+        &self,
+        size: SizePreset,
+        distributions: &BTreeMap<String, Distribution>,
+        seed: u64,
+    ) -> Result<String, String> {
+        let mut rng = Rng(seed);
+        let mut env = BTreeMap::new();
+        let mut result = String::new();
+        for spec in &self.lines {
+            let count = match &spec.repeat {
+                Some(repeat) => eval(repeat, &env),
+                None => 1,
+            };
+            for _ in 0..count {
+                let mut line_env = env.clone();
+                let mut tokens = vec![];
+                for decl in &spec.decls {
+                    let distribution = distributions
+                        .get(&decl.name)
+                        .copied()
+                        .unwrap_or(Distribution::Uniform);
+                    let len = decl.len.as_ref().map(|len| eval(len, &line_env));
+                    let is_scalar = len.is_none() && spec.repeat.is_none();
+                    for _ in 0..len.unwrap_or(1) {
+                        match &decl.kind {
+                            Kind::Int { min, max } => {
+                                let (min, max) = (eval(min, &line_env), eval(max, &line_env));
+                                if min > max {
+                                    return Err(format!(
+                                        "{} has no value in [{}, {}]",
+                                        decl.name, min, max
+                                    ));
+                                }
+                                let value = if is_scalar {
+                                    pick_size(size, distribution, min, max, &mut rng)
+                                } else {
+                                    pick(distribution, min, max, &mut rng)
+                                };
+                                if len.is_none() {
+                                    line_env.insert(decl.name.clone(), value);
+                                }
+                                tokens.push(value.to_string());
+                            }
+                            Kind::String {
+                                chars,
+                                min_len,
+                                max_len,
+                            } => {
+                                // Empty strings can't be separated by spaces.
+                                let min_len = std::cmp::max(eval(min_len, &line_env), 1);
+                                let max_len = eval(max_len, &line_env);
+                                if min_len > max_len {
+                                    return Err(format!(
+                                        "{} has no length in [{}, {}]",
+                                        decl.name, min_len, max_len
+                                    ));
+                                }
+                                let len = pick_size(size, distribution, min_len, max_len, &mut rng);
+                                let token: String =
+                                    (0..len).map(|_| pick_char(chars, &mut rng)).collect();
+                                tokens.push(token);
+                            }
+                        }
+                    }
+                }
+                if spec.repeat.is_none() {
+                    env = line_env;
+                }
+                result.push_str(&tokens.join(" "));
+                result.push('\n');
+            }
+        }
+        Ok(result)
+    }
+}
+
+// A small pseudo-random number generator (SplitMix64), so that the same seed gives the same input everywhere.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // A number in [0, n). n must be positive.
+    fn below(&mut self, n: u128) -> u128 {
+        let x = (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64());
+        x % n
+    }
+}
+
+// Picks a size-like value in [min, max] by the size preset.
+fn pick_size(
+    size: SizePreset,
+    distribution: Distribution,
+    min: i128,
+    max: i128,
+    rng: &mut Rng,
+) -> i128 {
+    match size {
+        SizePreset::Min => min,
+        SizePreset::Max => max,
+        SizePreset::Random => pick(distribution, min, max, rng),
+    }
+}
+
+// Picks a value in [min, max] by the distribution.
+fn pick(distribution: Distribution, min: i128, max: i128, rng: &mut Rng) -> i128 {
+    let width = (max - min) as u128 + 1;
+    match distribution {
+        Distribution::Uniform => min + rng.below(width) as i128,
+        Distribution::Log => {
+            // The offset from min has a uniformly random number of bits.
+            let bits = 128 - width.leading_zeros();
+            let bound = 1u128 << rng.below(u128::from(bits));
+            min + rng.below(std::cmp::min(bound, width)) as i128
+        }
+        Distribution::Edges => {
+            if rng.below(2) == 0 {
+                let edges = [min, min + 1, max - 1, max];
+                edges[rng.below(4) as usize].clamp(min, max)
+            } else {
+                min + rng.below(width) as i128
+            }
+        }
+    }
+}
+
+fn pick_char(chars: &[(char, char)], rng: &mut Rng) -> char {
+    let total: u128 = chars
+        .iter()
+        .map(|&(lo, hi)| u128::from(hi as u32 - lo as u32) + 1)
+        .sum();
+    let mut index = rng.below(total);
+    for &(lo, hi) in chars {
+        let count = u128::from(hi as u32 - lo as u32) + 1;
+        if index < count {
+            return char::from_u32(lo as u32 + index as u32).unwrap_or(lo);
+        }
+        index -= count;
+    }
+    unreachable!()
+}
+
+// Names of the variables in a line, for error messages.
+fn names(spec: &LineSpec) -> String {
+    let names: Vec<&str> = spec.decls.iter().map(|decl| decl.name.as_str()).collect();
+    names.join(", ")
+}
+
+// Checks a line, and stores the values of integers outside arrays in `env`.
+// Returns the column of the first problem and its description.
+fn validate_line(
+    spec: &LineSpec,
+    line: &str,
+    env: &mut BTreeMap<String, i128>,
+) -> Result<(), (usize, String)> {
+    // Tokens and their 1-indexed columns
+    let mut tokens = vec![];
+    let mut column = 1;
+    if !line.is_empty() {
+        for token in line.split(' ') {
+            if token.is_empty() {
+                return Err((column, "extra space".to_owned()));
+            }
+            tokens.push((column, token));
+            column += token.len() + 1;
+        }
+    }
+    let mut tokens = tokens.into_iter();
+    for decl in &spec.decls {
+        let len = decl.len.as_ref().map(|len| eval(len, env));
+        for i in 0..len.unwrap_or(1) {
+            let name = match len {
+                Some(_) => format!("{}[{}]", decl.name, i + 1),
+                None => decl.name.clone(),
+            };
+            let Some((column, token)) = tokens.next() else {
+                return Err((
+                    line.len() + 1,
+                    format!("expected {}, found the end of the line", name),
+                ));
+            };
+            match &decl.kind {
+                Kind::Int { min, max } => {
+                    let Some(value) = parse_int(token) else {
+                        return Err((
+                            column,
+                            format!("expected an integer for {}, found `{}`", name, token),
+                        ));
+                    };
+                    let (min, max) = (eval(min, env), eval(max, env));
+                    if value < min || max < value {
+                        return Err((
+                            column,
+                            format!("{} = {} is out of range [{}, {}]", name, value, min, max),
+                        ));
+                    }
+                    if len.is_none() {
+                        env.insert(decl.name.clone(), value);
+                    }
+                }
+                Kind::String {
+                    chars,
+                    min_len,
+                    max_len,
+                } => {
+                    for (offset, c) in token.char_indices() {
+                        if !chars.iter().any(|&(lo, hi)| lo <= c && c <= hi) {
+                            return Err((
+                                column + offset,
+                                format!("character `{}` of {} is not allowed", c, name),
+                            ));
+                        }
+                    }
+                    let (min_len, max_len) = (eval(min_len, env), eval(max_len, env));
+                    let token_len = token.chars().count() as i128;
+                    if token_len < min_len || max_len < token_len {
+                        return Err((
+                            column,
+                            format!(
+                                "length of {} = {} is out of range [{}, {}]",
+                                name, token_len, min_len, max_len
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    if let Some((column, _)) = tokens.next() {
+        return Err((column, "extra token".to_owned()));
+    }
+    Ok(())
+}
+
+// Parses an integer token strictly: no plus sign, no leading zeros and no "-0".
+fn parse_int(token: &str) -> Option<i128> {
+    let digits = token.strip_prefix('-').unwrap_or(token);
+    if digits.is_empty()
+        || !digits.bytes().all(|c| c.is_ascii_digit())
+        || (digits.len() > 1 && digits.starts_with('0'))
+        || token == "-0"
+    {
+        return None;
+    }
+    let value: i64 = token.parse().ok()?;
+    Some(value.into())
+}
+
+// Evaluates an expression. Every variable is checked to be declared when the format is parsed.
+fn eval(expr: &Expr, env: &BTreeMap<String, i128>) -> i128 {
+    match expr {
+        Expr::Num(v) => *v,
+        Expr::Var(name) => env[name],
+        Expr::Add(a, b) => eval(a, env) + eval(b, env),
+        Expr::Sub(a, b) => eval(a, env) - eval(b, env),
+        Expr::Mul(a, b) => eval(a, env) * eval(b, env),
+    }
+}
+
+// A cursor over a line of a format description.
+struct Parser<'a, 's> {
+    rest: &'a str,
+    // Integers that may appear in expressions
+    scalars: &'s [String],
+}
+
+impl<'a> Parser<'a, '_> {
+    fn skip_spaces(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        self.skip_spaces();
+        match self.rest.strip_prefix(s) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), String> {
+        if self.eat(s) {
+            Ok(())
+        } else {
+            Err(format!("expected `{}` before `{}`", s, self.rest))
+        }
+    }
+
+    fn ident(&mut self) -> Result<&'a str, String> {
+        self.skip_spaces();
+        let len = self
+            .rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(self.rest.len());
+        if len == 0 || self.rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(format!("expected a name before `{}`", self.rest));
+        }
+        let (ident, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Ok(ident)
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.term()?;
+        loop {
+            if self.eat("+") {
+                expr = Expr::Add(Box::new(expr), Box::new(self.term()?));
+            } else if self.eat("-") {
+                expr = Expr::Sub(Box::new(expr), Box::new(self.term()?));
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    // term := factor ('*' factor)*
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut expr = self.factor()?;
+        while self.eat("*") {
+            expr = Expr::Mul(Box::new(expr), Box::new(self.factor()?));
+        }
+        Ok(expr)
+    }
+
+    // factor := '-'? (number | name | '(' expr ')'), where a number may have an exponent like `2e5`
+    fn factor(&mut self) -> Result<Expr, String> {
+        if self.eat("-") {
+            let factor = self.factor()?;
+            return Ok(Expr::Sub(Box::new(Expr::Num(0)), Box::new(factor)));
+        }
+        if self.eat("(") {
+            let expr = self.expr()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+        self.skip_spaces();
+        if self.rest.starts_with(|c: char| c.is_ascii_digit()) {
+            let len = self
+                .rest
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(self.rest.len());
+            let (number, rest) = self.rest.split_at(len);
+            self.rest = rest;
+            let (mantissa, exponent) = number.split_once('e').unwrap_or((number, "0"));
+            let value = mantissa
+                .parse::<i128>()
+                .ok()
+                .zip(exponent.parse::<u32>().ok())
+                .and_then(|(m, e)| m.checked_mul(10i128.checked_pow(e)?));
+            return value
+                .map(Expr::Num)
+                .ok_or_else(|| format!("invalid number `{}`", number));
+        }
+        let name = self.ident()?;
+        if !self.scalars.iter().any(|x| x == name) {
+            return Err(format!(
+                "`{}` is not an integer declared on an earlier line",
+                name
+            ));
+        }
+        Ok(Expr::Var(name.to_owned()))
+    }
+
+    // range := '[' expr ',' expr ']'
+    fn range(&mut self) -> Result<(Expr, Expr), String> {
+        self.expect("[")?;
+        let lo = self.expr()?;
+        self.expect(",")?;
+        let hi = self.expr()?;
+        self.expect("]")?;
+        Ok((lo, hi))
+    }
+
+    // chars := '[' (c | c '-' c)+ ']'
+    fn chars(&mut self) -> Result<Vec<(char, char)>, String> {
+        self.expect("[")?;
+        let end = self
+            .rest
+            .find(']')
+            .ok_or_else(|| "expected `]` after characters".to_owned())?;
+        let chars: Vec<char> = self.rest[..end].chars().collect();
+        self.rest = &self.rest[end + 1..];
+        let mut result = vec![];
+        let mut i = 0;
+        while i < chars.len() {
+            if i + 2 < chars.len() && chars[i + 1] == '-' {
+                if chars[i] > chars[i + 2] {
+                    return Err(format!("invalid range `{}-{}`", chars[i], chars[i + 2]));
+                }
+                result.push((chars[i], chars[i + 2]));
+                i += 3;
+            } else {
+                result.push((chars[i], chars[i]));
+                i += 1;
+            }
+        }
+        if result.is_empty() {
+            return Err("no characters are allowed".to_owned());
+        }
+        Ok(result)
+    }
+
+    // decl := name ':' ('int' | 'string') ('[' expr ']')? ('in' range | 'of' chars | 'len' range)*
+    fn decl(&mut self) -> Result<Decl, String> {
+        let name = self.ident()?.to_owned();
+        self.expect(":")?;
+        let ty = self.ident()?;
+        let len = if self.eat("[") {
+            let len = self.expr()?;
+            self.expect("]")?;
+            Some(len)
+        } else {
+            None
+        };
+        let (mut range, mut chars, mut len_range) = (None, None, None);
+        loop {
+            if self.eat("in ") {
+                range = Some(self.range()?);
+            } else if self.eat("of ") {
+                chars = Some(self.chars()?);
+            } else if self.eat("len ") {
+                len_range = Some(self.range()?);
+            } else {
+                break;
+            }
+        }
+        let kind = match (ty, range, chars, len_range) {
+            ("int", Some((min, max)), None, None) => Kind::Int { min, max },
+            ("string", None, Some(chars), Some((min_len, max_len))) => Kind::String {
+                chars,
+                min_len,
+                max_len,
+            },
+            ("int", ..) => return Err(format!("{} needs only `in [MIN, MAX]`", name)),
+            ("string", ..) => {
+                return Err(format!(
+                    "{} needs only `of [CHARACTERS]` and `len [MIN, MAX]`",
+                    name
+                ))
+            }
+            _ => return Err(format!("unknown type `{}`", ty)),
+        };
+        Ok(Decl { name, len, kind })
+    }
+}
+
+// Parses a line of a format description.
+// Integers declared on it that can be used in later expressions are added to `scalars`.
+fn parse_line(line: &str, scalars: &mut Vec<String>) -> Result<LineSpec, String> {
+    let mut rest = line;
+    let mut repeat = None;
+    let mut decls = vec![];
+    // Integers on a line can be used later on the same line, e.g. `N: int in [1, 10], A: int[N] in [0, 9]`,
+    // but those on a repeated line can't be used on other lines.
+    let mut line_scalars = scalars.clone();
+    loop {
+        let mut parser = Parser {
+            rest,
+            scalars: &line_scalars,
+        };
+        if decls.is_empty() && repeat.is_none() && parser.eat("repeat ") {
+            repeat = Some(parser.expr()?);
+            parser.expect(":")?;
+        }
+        let decl = parser.decl()?;
+        let more = parser.eat(",");
+        parser.skip_spaces();
+        rest = parser.rest;
+        if decl.len.is_none() && matches!(decl.kind, Kind::Int { .. }) {
+            line_scalars.push(decl.name.clone());
+            if repeat.is_none() {
+                scalars.push(decl.name.clone());
+            }
+        }
+        decls.push(decl);
+        if !more {
+            break;
+        }
+    }
+    if !rest.is_empty() {
+        return Err(format!("unexpected `{}`", rest));
+    }
+    Ok(LineSpec { repeat, decls })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(lines: &[&str]) -> InputFormat {
+        let lines: Vec<String> = lines.iter().map(|x| x.to_string()).collect();
+        InputFormat::parse(&lines).unwrap()
+    }
+
+    #[test]
+    fn validate_accepts_valid_inputs() {
+        let format = format(&[
+            "N: int in [1, 2e5], K: int in [0, N]",
+            "A: int[N] in [-1e9, 1e9]",
+            "S: string of [a-z] len [1, N]",
+            "repeat K: U: int in [1, N], V: int in [1, N]",
+        ]);
+        format
+            .validate("3 2\n-5 0 1000000000\nabc\n1 2\n3 3\n")
+            .unwrap();
+        format.validate("1 0\n7\nz\n").unwrap();
+    }
+
+    #[test]
+    fn validate_reports_line_and_column() {
+        let format = format(&[
+            "N: int in [1, 2e5]",
+            "A: int[N] in [0, 9]",
+            "S: string of [a-z] len [1, N]",
+        ]);
+        let check = |input: &str, line, column| {
+            let e = format.validate(input).unwrap_err();
+            assert_eq!((e.line, e.column), (line, column), "{}", e);
+        };
+        check("0\n\nab\n", 1, 1);
+        check("2\n1 10\nab\n", 2, 3);
+        check("2\n1 2 3\nab\n", 2, 5);
+        check("2\n1\nab\n", 2, 2);
+        check("2\n1  2\nab\n", 2, 3);
+        check("2\n01 2\nab\n", 2, 1);
+        check("2\n1 2\naB\n", 3, 2);
+        check("2\n1 2\nabc\n", 3, 1);
+        check("2\n1 2\nab", 3, 3);
+        check("2\n1 2\nab\n\n", 4, 1);
+        check("2\n1 2\n", 3, 1);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_formats() {
+        for lines in [
+            vec!["A: int[N] in [0, 9]"],
+            vec!["N: int"],
+            vec!["N: float in [0, 1]"],
+            vec!["S: string of [a-z]"],
+            vec!["S: string of [z-a] len [1, 10]"],
+            vec!["N: int in [1, 10] M: int in [1, 10]"],
+            vec!["repeat 2: N: int in [1, 10]", "A: int[N] in [0, 9]"],
+        ] {
+            let lines: Vec<String> = lines.iter().map(|x| x.to_string()).collect();
+            assert!(InputFormat::parse(&lines).is_err(), "{:?}", lines);
+        }
+    }
+
+    #[test]
+    fn generate_follows_the_format() {
+        let format = format(&[
+            "N: int in [1, 20], K: int in [0, N]",
+            "A: int[N] in [-1e18, 1e18]",
+            "S: string of [a-c0-9] len [1, N]",
+            "repeat K: U: int in [1, N], V: int in [U, N]",
+        ]);
+        let mut distributions = BTreeMap::new();
+        distributions.insert("A".to_owned(), Distribution::Log);
+        distributions.insert("U".to_owned(), Distribution::Edges);
+        for size in [SizePreset::Min, SizePreset::Max, SizePreset::Random] {
+            for seed in 0..20 {
+                let input = format.generate(size, &distributions, seed).unwrap();
+                format.validate(&input).unwrap();
+            }
+        }
+        let min = format.generate(SizePreset::Min, &distributions, 0).unwrap();
+        assert!(min.starts_with("1 0\n"));
+        let max = format.generate(SizePreset::Max, &distributions, 0).unwrap();
+        assert!(max.starts_with("20 20\n"));
+        // The same seed gives the same input.
+        assert_eq!(
+            format.generate(SizePreset::Random, &distributions, 7),
+            format.generate(SizePreset::Random, &distributions, 7)
+        );
+    }
+
+    #[test]
+    fn generate_fails_on_empty_ranges() {
+        let format = format(&["N: int in [1, 10]", "A: int in [N + 1, 10]"]);
+        assert!(format
+            .generate(SizePreset::Max, &BTreeMap::new(), 0)
+            .is_err());
+    }
+}
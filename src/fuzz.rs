@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+/// An input made by breaking a valid input, which validators should reject.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InvalidInput {
+    /// Index of the valid input this was made from.
+    pub source: usize,
+    /// What was changed, e.g. "line 1, token 1: `200000` -> `200001`".
+    pub description: String,
+    /// The content of the input.
+    pub content: String,
+}
+
+// Where a number is in an input, for estimating its bounds.
+// Each token in the first line and in short lines is its own column, while all tokens in long lines share a column.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Column {
+    Header(usize),
+    Body(usize),
+    Array,
+}
+
+// Lines with more tokens than this are treated as arrays.
+const MAX_SHORT_LINE_TOKENS: usize = 3;
+
+/// Makes inputs with numbers just outside their bounds.
+/// Bounds are estimated from `inputs`: the largest and smallest number in each column of the inputs are taken as bounds,
+/// and the first occurrence of each is changed by one.
+pub fn out_of_bounds_inputs(inputs: &[String]) -> Vec<InvalidInput> {
+    // Extremes of each column: (value, input, line, token)
+    type Extreme = (i64, usize, usize, usize);
+    let mut extremes: BTreeMap<Column, (Extreme, Extreme)> = BTreeMap::new();
+    for (source, input) in inputs.iter().enumerate() {
+        for (i, line) in input.lines().enumerate() {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            for (j, token) in tokens.iter().enumerate() {
+                let Ok(v) = token.parse::<i64>() else {
+                    continue;
+                };
+                let column = if i == 0 {
+                    Column::Header(j)
+                } else if tokens.len() <= MAX_SHORT_LINE_TOKENS {
+                    Column::Body(j)
+                } else {
+                    Column::Array
+                };
+                let here = (v, source, i, j);
+                let (max, min) = extremes.entry(column).or_insert((here, here));
+                if v > max.0 {
+                    *max = here;
+                }
+                if v < min.0 {
+                    *min = here;
+                }
+            }
+        }
+    }
+    let mut result = vec![];
+    for (max, min) in extremes.into_values() {
+        for ((v, source, i, j), outside) in
+            [(max, max.0.checked_add(1)), (min, min.0.checked_sub(1))]
+        {
+            let Some(outside) = outside else {
+                continue;
+            };
+            result.push(InvalidInput {
+                source,
+                description: format!("line {}, token {}: `{}` -> `{}`", i + 1, j + 1, v, outside),
+                content: replace_token(&inputs[source], i, j, &outside.to_string()),
+            });
+        }
+    }
+    result
+}
+
+/// Makes inputs with broken formatting: extra or missing tokens, extra whitespace and a missing final newline.
+pub fn malformed_inputs(source: usize, input: &str) -> Vec<InvalidInput> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut result = vec![];
+    let mut push = |description: String, content: String| {
+        if content != input {
+            result.push(InvalidInput {
+                source,
+                description,
+                content,
+            });
+        }
+    };
+    if let Some(content) = input.strip_suffix('\n') {
+        push("missing final newline".to_owned(), content.to_owned());
+    }
+    push(
+        "extra empty line at the end".to_owned(),
+        format!("{}\n", input),
+    );
+    // The first and the last lines are broken.
+    let mut targets = vec![0];
+    if lines.len() > 1 {
+        targets.push(lines.len() - 1);
+    }
+    for i in targets {
+        let Some(line) = lines.get(i) else {
+            continue;
+        };
+        let tokens: Vec<&str> = line.split(' ').collect();
+        if let Some(&last) = tokens.last() {
+            push(
+                format!("line {}: extra token", i + 1),
+                replace_line(input, i, &format!("{} {}", line, last)),
+            );
+        }
+        if !line.is_empty() {
+            push(
+                format!("line {}: missing token", i + 1),
+                replace_line(input, i, &tokens[..tokens.len() - 1].join(" ")),
+            );
+            push(
+                format!("line {}: trailing space", i + 1),
+                replace_line(input, i, &format!("{} ", line)),
+            );
+            push(
+                format!("line {}: leading space", i + 1),
+                replace_line(input, i, &format!(" {}", line)),
+            );
+        }
+        if tokens.len() >= 2 {
+            push(
+                format!("line {}: double space", i + 1),
+                replace_line(input, i, &tokens.join("  ")),
+            );
+        }
+    }
+    result
+}
+
+fn replace_line(input: &str, index: usize, new_line: &str) -> String {
+    let mut lines: Vec<&str> = input.split('\n').collect();
+    lines[index] = new_line;
+    lines.join("\n")
+}
+
+fn replace_token(input: &str, line: usize, token: usize, new_token: &str) -> String {
+    let lines: Vec<&str> = input.split('\n').collect();
+    let mut tokens: Vec<&str> = lines[line].split_whitespace().collect();
+    tokens[token] = new_token;
+    replace_line(input, line, &tokens.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_bounds_inputs_work() {
+        let inputs = vec!["3\n1 2 3 4\n".to_owned(), "5\n0 9 1 1\n".to_owned()];
+        let contents: Vec<String> = out_of_bounds_inputs(&inputs)
+            .into_iter()
+            .map(|x| x.content)
+            .collect();
+        assert_eq!(
+            contents,
+            vec![
+                "6\n0 9 1 1\n",
+                "2\n1 2 3 4\n",
+                "5\n0 10 1 1\n",
+                "5\n-1 9 1 1\n"
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_inputs_work() {
+        let inputs = malformed_inputs(0, "2\n1 2\n");
+        let contents: Vec<&str> = inputs.iter().map(|x| x.content.as_str()).collect();
+        for expected in [
+            "2\n1 2",
+            "2\n1 2\n\n",
+            "2 2\n1 2\n",
+            "\n1 2\n",
+            "2\n1 2 \n",
+            "2\n1  2\n",
+        ] {
+            assert!(contents.contains(&expected), "{:?}", expected);
+        }
+        assert!(inputs.iter().all(|x| x.content != "2\n1 2\n"));
+    }
+}
@@ -55,6 +55,11 @@ pub trait IoUtil {
     fn to_absolute(&self, path: &Path) -> Result<PathBuf> {
         unreachable!()
     }
+    /// Get the size of a file in bytes.
+    #[allow(unused)]
+    fn file_size(&self, path: &Path) -> Result<u64> {
+        unreachable!()
+    }
     /// List a directory. Return a list of paths relative to `path`.
     #[allow(unused)]
     fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
@@ -65,6 +70,17 @@ pub trait IoUtil {
     fn remove_dir_all(&self, path: &Path) -> Result<()> {
         unreachable!()
     }
+    /// Remove a file. It is not an error if the file doesn't exist.
+    #[allow(unused)]
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        unreachable!()
+    }
+    /// Write content to a file, replacing the file if it exists.
+    /// If some of the intermediate directories are missing, they will be created.
+    #[allow(unused)]
+    fn overwrite_file(&self, filepath: &Path, content: &[u8]) -> Result<()> {
+        unreachable!()
+    }
 }
 
 pub trait IoUtilExt {}
@@ -103,11 +119,7 @@ impl<T: IoUtilExt> IoUtil for T {
             std::fs::create_dir(path)?;
             return Ok(());
         }
-        Err(IOError::new(
-            ErrorKind::Other,
-            format!("not a directory: {}", path.display()),
-        )
-        .into())
+        Err(IOError::other(format!("not a directory: {}", path.display())).into())
     }
     fn write_str_to_file(&self, file: &mut dyn Write, s: &str) -> Result<()> {
         write!(file, "{}", s)?;
@@ -138,6 +150,9 @@ impl<T: IoUtilExt> IoUtil for T {
         .clean();
         Ok(path)
     }
+    fn file_size(&self, path: &Path) -> Result<u64> {
+        Ok(std::fs::metadata(path)?.len())
+    }
     fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
         let mut result = vec![];
         for entry in std::fs::read_dir(path)? {
@@ -160,6 +175,21 @@ impl<T: IoUtilExt> IoUtil for T {
         }
         Ok(())
     }
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        if let Err(e) = std::fs::remove_file(path) {
+            if e.kind() != ErrorKind::NotFound {
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+    fn overwrite_file(&self, filepath: &Path, content: &[u8]) -> Result<()> {
+        if let Some(parent) = filepath.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(filepath, content)?;
+        Ok(())
+    }
 }
 
 pub struct IoUtilImpl;
@@ -1,5 +1,17 @@
 pub mod cmd;
+pub mod constants;
+pub mod coverage;
+pub mod diff;
 pub mod entity;
 pub mod error;
+pub mod format;
+pub mod fuzz;
 pub mod io_util;
+pub mod lint;
+pub mod minimize;
+pub mod mutate;
+pub mod par_util;
 pub mod run_util;
+pub mod statement;
+pub mod stats;
+pub mod testlib;
@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+
+/// A formatting problem in a testcase file.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FormatProblem {
+    /// 1-indexed line number.
+    pub line: usize,
+    /// What is wrong, e.g. "trailing space".
+    pub description: &'static str,
+}
+
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Finds formatting problems that judges often reject: a BOM, CRLF line endings, trailing spaces, tabs
+/// and a missing final newline. Each problem is reported once per line. An empty file has no problems.
+pub fn format_problems(content: &[u8]) -> Vec<FormatProblem> {
+    let mut result = vec![];
+    if content.starts_with(BOM) {
+        result.push(FormatProblem {
+            line: 1,
+            description: "byte order mark",
+        });
+    }
+    let mut lines: Vec<&[u8]> = content.split(|&c| c == b'\n').collect();
+    // The empty slice after the final newline is not a line.
+    if content.ends_with(b"\n") || content.is_empty() {
+        lines.pop();
+    }
+    for (i, line) in lines.iter().enumerate() {
+        let line_number = i + 1;
+        let mut push = |description| {
+            result.push(FormatProblem {
+                line: line_number,
+                description,
+            })
+        };
+        let line = match line.strip_suffix(b"\r") {
+            Some(line) => {
+                push("CRLF line ending");
+                line
+            }
+            None => line,
+        };
+        if line.contains(&b'\t') {
+            push("tab");
+        }
+        if line.ends_with(b" ") || line.ends_with(b"\t") {
+            push("trailing space");
+        }
+    }
+    if !content.is_empty() && !content.ends_with(b"\n") {
+        result.push(FormatProblem {
+            line: lines.len(),
+            description: "missing final newline",
+        });
+    }
+    result
+}
+
+/// Groups testcases whose contents are the same up to whitespace.
+/// Each group is a list of sets of indices of testcases with identical contents, and is returned only if it has
+/// two or more testcases. Indices in each set, sets in each group, and groups are in ascending order.
+pub fn duplicate_groups(contents: &[&[u8]]) -> Vec<Vec<Vec<usize>>> {
+    let mut groups: BTreeMap<Vec<&[u8]>, Vec<Vec<usize>>> = BTreeMap::new();
+    for (i, &content) in contents.iter().enumerate() {
+        let tokens = content
+            .split(|c| c.is_ascii_whitespace())
+            .filter(|token| !token.is_empty())
+            .collect();
+        let group = groups.entry(tokens).or_default();
+        match group.iter_mut().find(|set| contents[set[0]] == content) {
+            Some(set) => set.push(i),
+            None => group.push(vec![i]),
+        }
+    }
+    let mut result: Vec<_> = groups
+        .into_values()
+        .filter(|group| group.iter().map(|set| set.len()).sum::<usize>() >= 2)
+        .collect();
+    result.sort();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptions(content: &[u8]) -> Vec<(usize, &'static str)> {
+        format_problems(content)
+            .into_iter()
+            .map(|x| (x.line, x.description))
+            .collect()
+    }
+
+    #[test]
+    fn format_problems_works() {
+        assert_eq!(descriptions(b""), vec![]);
+        assert_eq!(descriptions(b"2\n1 2\n"), vec![]);
+        assert_eq!(
+            descriptions(b"\xef\xbb\xbf2\r\n1\t2 \n3"),
+            vec![
+                (1, "byte order mark"),
+                (1, "CRLF line ending"),
+                (2, "tab"),
+                (2, "trailing space"),
+                (3, "missing final newline"),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_groups_works() {
+        let contents: [&[u8]; 5] = [b"1 2\n", b"3\n", b"1  2\n", b"1 2\n", b"1\n2\n"];
+        assert_eq!(
+            duplicate_groups(&contents),
+            vec![vec![vec![0, 3], vec![2], vec![4]]]
+        );
+        assert!(duplicate_groups(&contents[..2]).is_empty());
+    }
+}
@@ -1,7 +1,10 @@
 #[macro_use]
 extern crate clap;
 use clap::Command as ClapCommand;
-use creo::cmd::{add, all, check, gen, init, refgen, test, val, Command};
+use creo::cmd::{
+    add, all, check, constants, coverage, gen, init, minimize, mutate, refgen, run, samples,
+    statement, stats, stress, test, translations, val, Command,
+};
 use creo::entity::project::ProjectImpl;
 
 fn main() {
@@ -16,15 +19,45 @@ fn main() {
         &mut check::CheckCommand {
             project: ProjectImpl,
         },
+        &mut constants::ConstantsCommand {
+            project: ProjectImpl,
+        },
+        &mut coverage::CoverageCommand {
+            project: ProjectImpl,
+        },
         &mut gen::GenCommand {
             project: ProjectImpl,
         },
+        &mut minimize::MinimizeCommand {
+            project: ProjectImpl,
+        },
+        &mut mutate::MutateCommand {
+            project: ProjectImpl,
+        },
         &mut refgen::RefGenCommand {
             project: ProjectImpl,
         },
+        &mut run::RunCommand {
+            project: ProjectImpl,
+        },
+        &mut samples::SamplesCommand {
+            project: ProjectImpl,
+        },
+        &mut statement::StatementCommand {
+            project: ProjectImpl,
+        },
+        &mut stats::StatsCommand {
+            project: ProjectImpl,
+        },
+        &mut stress::StressCommand {
+            project: ProjectImpl,
+        },
         &mut test::TestCommand {
             project: ProjectImpl,
         },
+        &mut translations::TranslationsCommand {
+            project: ProjectImpl,
+        },
         &mut val::ValCommand {
             project: ProjectImpl,
         },
@@ -0,0 +1,179 @@
+/// An edit that may make an input smaller.
+/// Lines are separated by '\n', and tokens in a line are separated by spaces.
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Edit {
+    /// Removes lines in [start, end).
+    RemoveLines(usize, usize),
+    /// Decrements the number at (line, index) and removes the last line.
+    DecrementAndRemoveLastLine(usize, usize),
+    /// Decrements the number at (line, index) and removes the last token of each later line
+    /// that has as many tokens as the number.
+    DecrementAndShortenLines(usize, usize),
+    /// Removes the token at (line, index).
+    RemoveToken(usize, usize),
+    /// Replaces the token at (line, index) with a number.
+    Replace(usize, usize, i64),
+}
+
+/// Returns inputs made by small edits of `input`, the most promising first.
+/// The edits keep the layout of lines and tokens, so that inputs like "N / a_1 ... a_N" stay well-formed
+/// as often as possible. Some of the returned inputs may be larger than `input`; see is_smaller.
+pub fn candidates(input: &str) -> impl Iterator<Item = String> {
+    let lines: Vec<Vec<String>> = input
+        .split('\n')
+        .map(|line| line.split(' ').map(|token| token.to_owned()).collect())
+        .collect();
+    // The empty line after the final newline is never removed.
+    let content_lines = if input.ends_with('\n') {
+        lines.len() - 1
+    } else {
+        lines.len()
+    };
+    let numbers: Vec<(usize, usize, i64)> = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            line.iter()
+                .enumerate()
+                .filter_map(move |(j, token)| token.parse().ok().map(|v| (i, j, v)))
+        })
+        .collect();
+
+    let mut edits = vec![];
+    let mut size = content_lines / 2;
+    while size >= 1 {
+        for start in (0..content_lines).step_by(size) {
+            edits.push(Edit::RemoveLines(
+                start,
+                std::cmp::min(start + size, content_lines),
+            ));
+        }
+        size /= 2;
+    }
+    for &(i, j, v) in &numbers {
+        if v > 0 {
+            edits.push(Edit::DecrementAndRemoveLastLine(i, j));
+            edits.push(Edit::DecrementAndShortenLines(i, j));
+        }
+    }
+    for (i, line) in lines.iter().enumerate() {
+        for j in 0..line.len() {
+            edits.push(Edit::RemoveToken(i, j));
+        }
+    }
+    for &(i, j, v) in &numbers {
+        let mut targets = if v > 0 {
+            vec![0, 1, v / 2, v - 1]
+        } else {
+            vec![0, v / 2, v + 1]
+        };
+        targets.retain(|&target| target.unsigned_abs() < v.unsigned_abs());
+        targets.dedup();
+        for target in targets {
+            edits.push(Edit::Replace(i, j, target));
+        }
+    }
+    edits
+        .into_iter()
+        .filter_map(move |edit| apply(&lines, content_lines, &edit))
+}
+
+/// Is `a` smaller than `b`?
+/// Inputs are ordered first by length and then lexicographically, so that minimization always terminates.
+pub fn is_smaller(a: &str, b: &str) -> bool {
+    (a.len(), a) < (b.len(), b)
+}
+
+// Applies an edit. Returns None if the edit doesn't apply.
+fn apply(lines: &[Vec<String>], content_lines: usize, edit: &Edit) -> Option<String> {
+    let mut lines = lines.to_vec();
+    match *edit {
+        Edit::RemoveLines(start, end) => {
+            lines.drain(start..end);
+        }
+        Edit::DecrementAndRemoveLastLine(i, j) => {
+            if content_lines == 0 || content_lines - 1 <= i {
+                return None;
+            }
+            decrement(&mut lines[i][j])?;
+            lines.remove(content_lines - 1);
+        }
+        Edit::DecrementAndShortenLines(i, j) => {
+            let v: usize = lines[i][j].parse().ok()?;
+            decrement(&mut lines[i][j])?;
+            let mut shortened = false;
+            for line in &mut lines[i + 1..content_lines] {
+                if line.len() == v {
+                    line.pop();
+                    shortened = true;
+                }
+            }
+            if !shortened {
+                return None;
+            }
+        }
+        Edit::RemoveToken(i, j) => {
+            if lines[i][j].is_empty() {
+                return None;
+            }
+            lines[i].remove(j);
+        }
+        Edit::Replace(i, j, v) => lines[i][j] = v.to_string(),
+    }
+    Some(
+        lines
+            .iter()
+            .map(|line| line.join(" "))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+fn decrement(token: &mut String) -> Option<()> {
+    let v: i64 = token.parse().ok()?;
+    *token = (v - 1).to_string();
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_remove_lines_first() {
+        let result: Vec<String> = candidates("1\n2\n3\n4\n").collect();
+        assert_eq!(result[0], "3\n4\n");
+        assert_eq!(result[1], "1\n2\n");
+        // The final newline is kept.
+        assert!(result.iter().all(|c| c.ends_with('\n')));
+    }
+
+    #[test]
+    fn candidates_keep_counts_consistent() {
+        let result: Vec<String> = candidates("3\n1 2 3\n").collect();
+        assert!(result.contains(&"2\n1 2\n".to_owned()));
+        let result: Vec<String> = candidates("2\n5\n6\n").collect();
+        assert!(result.contains(&"1\n5\n".to_owned()));
+    }
+
+    #[test]
+    fn candidates_shrink_numbers() {
+        let result: Vec<String> = candidates("1 10\n").collect();
+        for expected in ["1 0\n", "1 5\n", "1 9\n", "0 10\n"] {
+            assert!(result.contains(&expected.to_owned()), "{}", expected);
+        }
+        let result: Vec<String> = candidates("-4\n").collect();
+        assert!(result.contains(&"-3\n".to_owned()));
+        assert!(!result.contains(&"-5\n".to_owned()));
+        let result: Vec<String> = candidates("-9223372036854775808\n").collect();
+        assert!(result.contains(&"-9223372036854775807\n".to_owned()));
+    }
+
+    #[test]
+    fn is_smaller_works() {
+        assert!(is_smaller("9", "10"));
+        assert!(is_smaller("10", "11"));
+        assert!(!is_smaller("10", "10"));
+        assert!(!is_smaller("100", "99"));
+    }
+}
@@ -0,0 +1,204 @@
+/// A source file with one small change.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Mutant {
+    /// 1-indexed line number of the change.
+    pub line: usize,
+    /// What was changed, e.g. "`<` -> `<=`".
+    pub description: String,
+    /// The changed source.
+    pub source: String,
+}
+
+/// Operators and what they are changed to.
+/// Longer operators come first, so that e.g. `<=` is not read as `<`.
+const OPERATORS: &[(&str, Option<&str>)] = &[
+    ("<<=", None),
+    (">>=", None),
+    ("<<", None),
+    (">>", None),
+    ("<=", Some("<")),
+    (">=", Some(">")),
+    ("==", Some("!=")),
+    ("!=", Some("==")),
+    ("&&", Some("||")),
+    ("||", Some("&&")),
+    ("++", None),
+    ("--", None),
+    ("+=", Some("-=")),
+    ("-=", Some("+=")),
+    ("->", None),
+    ("<", Some("<=")),
+    (">", Some(">=")),
+    ("+", Some("-")),
+    ("-", Some("+")),
+];
+
+/// Identifiers (or sequences of them) and what they are changed to.
+/// Longer sequences come first, so that e.g. `long long int` is changed as a whole.
+const IDENTIFIERS: &[(&str, &str)] = &[
+    ("long long int", "int"),
+    ("long long", "int"),
+    ("int", "short"),
+    ("true", "false"),
+    ("false", "true"),
+];
+
+/// Identifiers after which a type can't be changed to `short`.
+const TYPE_MODIFIERS: &[&str] = &["long", "short", "signed", "unsigned"];
+
+/// Makes mutants of a source file in a C-like language:
+/// integer literals are changed by one, comparison and arithmetic operators are swapped,
+/// `long long` and `int` are narrowed, and `true` and `false` are swapped.
+/// The return type of `main` is never changed.
+/// Comments, string and character literals, and preprocessor lines are left as is.
+pub fn mutants(source: &str) -> Vec<Mutant> {
+    let bytes = source.as_bytes();
+    let mut result = vec![];
+    let mut line = 1;
+    let mut line_start = true;
+    let mut i = 0;
+    let mut previous_identifier = "";
+    let push = |result: &mut Vec<Mutant>, line, start: usize, end: usize, to: &str| {
+        let mut mutated = source[..start].to_owned();
+        mutated.push_str(to);
+        mutated.push_str(&source[end..]);
+        result.push(Mutant {
+            line,
+            description: format!("`{}` -> `{}`", &source[start..end], to),
+            source: mutated,
+        });
+    };
+    while i < bytes.len() {
+        let rest = &source[i..];
+        let c = bytes[i];
+        if c == b'\n' {
+            line += 1;
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if c == b' ' || c == b'\t' || c == b'\r' {
+            i += 1;
+            continue;
+        }
+        let at_line_start = std::mem::replace(&mut line_start, false);
+        // Skip what must not be changed
+        let skip_to = if at_line_start && c == b'#' || rest.starts_with("//") {
+            rest.find('\n').map_or(bytes.len(), |end| i + end)
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            comment
+                .find("*/")
+                .map_or(bytes.len(), |end| i + 2 + end + 2)
+        } else if c == b'"' || c == b'\'' {
+            let mut end = i + 1;
+            while end < bytes.len() && bytes[end] != c {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            std::cmp::min(end + 1, bytes.len())
+        } else {
+            i
+        };
+        if skip_to > i {
+            line += source[i..skip_to].matches('\n').count();
+            i = skip_to;
+            continue;
+        }
+
+        if c.is_ascii_alphabetic() || c == b'_' {
+            let is_word_end = |len: usize| {
+                !rest[len..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+            };
+            let mut len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            if let Some(&(from, to)) = IDENTIFIERS
+                .iter()
+                .find(|&&(from, _)| rest.starts_with(from) && is_word_end(from.len()))
+            {
+                let is_main = rest[from.len()..].trim_start().starts_with("main(");
+                if !is_main && !TYPE_MODIFIERS.contains(&previous_identifier) {
+                    push(&mut result, line, i, i + from.len(), to);
+                }
+                len = from.len();
+            }
+            previous_identifier = &rest[..len];
+            i += len;
+            continue;
+        }
+        previous_identifier = "";
+        if c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '_')
+                .unwrap_or(rest.len());
+            // Only decimal integers without suffixes are changed.
+            if let Ok(v) = rest[..len].parse::<u64>() {
+                if let Some(next) = v.checked_add(1) {
+                    push(&mut result, line, i, i + len, &next.to_string());
+                }
+                if v > 0 {
+                    push(&mut result, line, i, i + len, &(v - 1).to_string());
+                }
+            }
+            i += len;
+            continue;
+        }
+        if let Some(&(from, to)) = OPERATORS.iter().find(|&&(from, _)| rest.starts_with(from)) {
+            if let Some(to) = to {
+                push(&mut result, line, i, i + from.len(), to);
+            }
+            i += from.len();
+            continue;
+        }
+        i += rest.chars().next().map_or(1, |c| c.len_utf8());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutants_change_operators_and_literals() {
+        let mutants = mutants("int f() {\n  if (a < 10) return 0;\n}\n");
+        let sources: Vec<&str> = mutants.iter().map(|m| m.source.as_str()).collect();
+        assert!(sources.contains(&"short f() {\n  if (a < 10) return 0;\n}\n"));
+        assert!(sources.contains(&"int f() {\n  if (a <= 10) return 0;\n}\n"));
+        assert!(sources.contains(&"int f() {\n  if (a < 11) return 0;\n}\n"));
+        assert!(sources.contains(&"int f() {\n  if (a < 9) return 0;\n}\n"));
+        assert!(sources.contains(&"int f() {\n  if (a < 10) return 1;\n}\n"));
+        assert_eq!(mutants.len(), 5);
+        let lt = mutants
+            .iter()
+            .find(|m| m.description == "`<` -> `<=`")
+            .unwrap();
+        assert_eq!(lt.line, 2);
+    }
+
+    #[test]
+    fn mutants_skip_increments_that_overflow() {
+        let mutants = mutants("x = 18446744073709551615;\n");
+        assert_eq!(mutants.len(), 1);
+        assert_eq!(mutants[0].source, "x = 18446744073709551614;\n");
+    }
+
+    #[test]
+    fn mutants_narrow_types() {
+        let mutants = mutants("int main() {\n  long long int a;\n  unsigned int b;\n}\n");
+        assert_eq!(mutants.len(), 1);
+        assert_eq!(
+            mutants[0].source,
+            "int main() {\n  int a;\n  unsigned int b;\n}\n"
+        );
+    }
+
+    #[test]
+    fn mutants_skip_comments_strings_and_preprocessor_lines() {
+        let source = "#include <vector>\n// a < b\n/* 1 + 2\n */ s = \"x < 1\"; c = '-';\n";
+        assert_eq!(mutants(source), vec![]);
+        // `<=` is one operator, and `->` is never changed.
+        let mutants = mutants("a <= b; p->x;\n");
+        assert_eq!(mutants.len(), 1);
+        assert_eq!(mutants[0].source, "a < b; p->x;\n");
+    }
+}
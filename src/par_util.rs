@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+/// Calls `work` on every item using up to `workers` threads, and `on_done` on the results in the order of items.
+///
+/// `work` receives the index of the worker thread (in `0..workers`), the index of the item, and the item.
+/// `on_done` is called on the calling thread as soon as the results of all preceding items are available,
+/// so the log printed in `on_done` is in the same order as items regardless of `workers`.
+pub fn run_in_order<T, R, W, D>(items: &[T], workers: usize, work: W, mut on_done: D)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(usize, usize, &T) -> R + Sync,
+    D: FnMut(usize, R),
+{
+    if workers <= 1 || items.len() <= 1 {
+        for (index, item) in items.iter().enumerate() {
+            let result = work(0, index, item);
+            on_done(index, result);
+        }
+        return;
+    }
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    std::thread::scope(|scope| {
+        for worker in 0..std::cmp::min(workers, items.len()) {
+            let sender = sender.clone();
+            let next = &next;
+            let work = &work;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                if index >= items.len() {
+                    break;
+                }
+                let result = work(worker, index, &items[index]);
+                if sender.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&expected) {
+                on_done(expected, result);
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn run_in_order_keeps_order() {
+        let items: Vec<u64> = (0..20).collect();
+        for workers in [1, 4] {
+            let mut done = vec![];
+            run_in_order(
+                &items,
+                workers,
+                |worker, _, &item| {
+                    assert!(worker < workers);
+                    // Later items finish earlier.
+                    std::thread::sleep(Duration::from_millis(20 - item));
+                    item * 2
+                },
+                |index, result| done.push((index, result)),
+            );
+            let expected: Vec<(usize, u64)> = (0..20).map(|i| (i as usize, i * 2)).collect();
+            assert_eq!(done, expected);
+        }
+    }
+}
@@ -1,11 +1,119 @@
 use sha2::{Digest, Sha256};
-use std::io::{Error as IOError, ErrorKind, Write};
+use std::fs::File;
+use std::io::{Error as IOError, ErrorKind, Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
 
-use crate::error::Result;
+use crate::constants::{CPP_HEADER, PYTHON_MODULE, RUST_FILE};
+use crate::error::{Error, Result};
 use crate::io_util::{IoUtil, IoUtilExt};
 
+/// Where compiled binaries and other cached files are placed.
+pub const CACHE_DIR: &str = "/tmp/creo-cache/";
+
+/// CPUs a run can be pinned to are numbered below this (CPU_SETSIZE on Linux).
+pub const MAX_CPUS: usize = 1024;
+
+/// Computes the SHA-256 hash of the concatenation of chunks as a hex string.
+pub fn sha256_hex(chunks: &[&[u8]]) -> String {
+    let mut hasher: Sha256 = Sha256::new();
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    let hash_val = hasher.finalize();
+    let mut hash_str = String::with_capacity(64);
+    for &val in &hash_val {
+        hash_str += &format!("{:02x}", val);
+    }
+    hash_str
+}
+
+/// Resource limits applied to a single run.
+#[derive(Clone, PartialEq, PartialOrd, Debug)]
+pub struct Limits {
+    /// Time limit in seconds.
+    pub time_limit: f64,
+    /// Memory limit in MiB.
+    pub memory_limit: u64,
+    /// If given, the process runs only on this CPU.
+    pub cpu: Option<usize>,
+}
+
+impl Limits {
+    /// Serializes the limits that affect the result of a run, for keys of recorded results.
+    /// The CPU is left out: it only decides where the process runs.
+    pub fn to_key(&self) -> String {
+        format!(
+            "time_limit={},memory_limit={}",
+            self.time_limit, self.memory_limit
+        )
+    }
+}
+
+/// What happened in a single run under resource limits.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct RunResult {
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    /// Exit code, if the process exited normally.
+    pub exit_code: Option<i32>,
+    /// The signal that terminated the process, if any.
+    pub signal: Option<i32>,
+    /// Wall-clock time.
+    pub elapsed: Duration,
+    /// Maximum resident set size in KiB, if available.
+    pub max_rss: Option<u64>,
+    /// Whether the process was killed because it exceeded the time limit.
+    pub timed_out: bool,
+}
+
+impl RunResult {
+    /// Did the process exit with status code 0?
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// Did the process use more memory than the limit?
+    pub fn exceeds_memory(&self, limits: &Limits) -> bool {
+        self.max_rss
+            .is_some_and(|rss| rss > limits.memory_limit * 1024)
+    }
+
+    /// A human-readable summary of used resources.
+    pub fn resources(&self) -> String {
+        let status = match (self.exit_code, self.signal) {
+            (Some(code), _) => format!("exit code {}", code),
+            (None, Some(signal)) => format!("signal {}", signal),
+            (None, None) => "unknown status".to_owned(),
+        };
+        let memory = match self.max_rss {
+            Some(rss) => format!("{} KiB", rss),
+            None => "unknown".to_owned(),
+        };
+        format!(
+            "time = {:.3}s{}, memory = {}, {}",
+            self.elapsed.as_secs_f64(),
+            if self.timed_out { " (killed)" } else { "" },
+            memory,
+            status,
+        )
+    }
+}
+
+// Puts cd at the front of PYTHONPATH.
+// A Python source runs from its copy in CACHE_DIR, so without this it cannot import
+// modules next to the original, such as the generated constants.
+fn python_path(cd: &Path) -> Result<std::ffi::OsString> {
+    let mut paths = vec![cd.to_path_buf()];
+    if let Some(old) = std::env::var_os("PYTHONPATH") {
+        paths.extend(std::env::split_paths(&old));
+    }
+    let joined =
+        std::env::join_paths(paths).map_err(|e| IOError::new(ErrorKind::InvalidInput, e))?;
+    Ok(joined)
+}
+
 /// Utility trait for compiling/running executables.
 pub trait RunUtil {
     /// Compiles a file into a temporary file and returns the path to the temporary file.
@@ -46,28 +154,48 @@ pub trait RunUtil {
     fn build_command(&self, run: &[String], infile: &Path, outfile: &Path) -> Vec<String> {
         unreachable!()
     }
+    /// Runs an executable with an input file under resource limits.
+    /// Unlike run_with_input, a failure of the executable is not an error;
+    /// it is reported in the returned RunResult.
+    #[allow(unused)]
+    fn run_with_limits(
+        &self,
+        cd: &Path,
+        exec: &Path,
+        run: &[String],
+        infile: &Path,
+        limits: &Limits,
+    ) -> Result<RunResult> {
+        unreachable!()
+    }
 }
 
 pub trait RunUtilExt: IoUtil {}
 impl<T: RunUtilExt> RunUtil for T {
     fn compile(&mut self, cd: &Path, src: &Path, compile: &[String]) -> Result<PathBuf> {
-        let tempdir = Path::new("/tmp/creo-cache/");
+        let tempdir = Path::new(CACHE_DIR);
         self.mkdir_p(tempdir)?;
-        // Compute a hash value from compile and the content of src.
-        let mut hash_str = String::with_capacity(64);
-        {
+        // Compute a hash value from compile and the content of src,
+        // together with the constants generated next to it, which src may include.
+        let hash_str = {
             let mut handle = self.open_file_for_read(src)?;
             let content = self.read_bytes_from_file(&mut handle)?;
-            let mut hasher: Sha256 = Sha256::new();
-            for c in compile {
-                hasher.update(c.as_bytes());
+            let mut generated = vec![];
+            for name in [CPP_HEADER, PYTHON_MODULE, RUST_FILE] {
+                let content = match self.open_file_for_read(&src.with_file_name(name)) {
+                    Ok(mut handle) => self.read_bytes_from_file(&mut handle)?,
+                    Err(Error::IOError(e)) if e.kind() == ErrorKind::NotFound => vec![],
+                    Err(e) => return Err(e),
+                };
+                // Prefix the length so that the boundaries between the files are unambiguous.
+                generated.push((content.len() as u64).to_le_bytes().to_vec());
+                generated.push(content);
             }
-            hasher.update(&content);
-            let hash_val = hasher.finalize();
-            for &val in &hash_val {
-                hash_str += &format!("{:02x}", val);
-            }
-        }
+            let mut chunks: Vec<&[u8]> = compile.iter().map(|c| c.as_bytes()).collect();
+            chunks.push(&content);
+            chunks.extend(generated.iter().map(|c| c.as_slice()));
+            sha256_hex(&chunks)
+        };
         let outpath = tempdir.join(hash_str);
         // If there exists an already compiled binary, return early.
         if outpath.is_file() {
@@ -75,7 +203,8 @@ impl<T: RunUtilExt> RunUtil for T {
                 "File {} exists: skipping compilation (source: {})",
                 outpath.to_str().unwrap(),
                 src.display(),
-            )
+            );
+            return Ok(outpath);
         }
         let compile = self.build_command(compile, src, &outpath);
         let prog = &compile[0];
@@ -105,7 +234,11 @@ impl<T: RunUtilExt> RunUtil for T {
         }
         let prog = &run[0];
         let args = run[1..].to_vec();
-        let status = Command::new(prog).args(&args).current_dir(cd).status()?;
+        let status = Command::new(prog)
+            .args(&args)
+            .current_dir(cd)
+            .env("PYTHONPATH", python_path(cd)?)
+            .status()?;
         if !status.success() {
             let err = IOError::new(
                 ErrorKind::InvalidData,
@@ -141,6 +274,7 @@ impl<T: RunUtilExt> RunUtil for T {
             .stdout(Stdio::piped())
             .stderr(Stdio::null())
             .current_dir(cd)
+            .env("PYTHONPATH", python_path(cd)?)
             .spawn()?;
         let inproc;
         {
@@ -200,6 +334,176 @@ impl<T: RunUtilExt> RunUtil for T {
         }
         compile
     }
+    fn run_with_limits(
+        &self,
+        cd: &Path,
+        exec: &Path,
+        run: &[String],
+        infile: &Path,
+        limits: &Limits,
+    ) -> Result<RunResult> {
+        let mut run = run.to_vec();
+        for v in run.iter_mut() {
+            if *v == "$OUT" {
+                *v = exec.to_str().unwrap().to_owned();
+            }
+        }
+        let prog = &run[0];
+        let args = run[1..].to_vec();
+        let mut command = Command::new(prog);
+        command
+            .args(&args)
+            .stdin(Stdio::from(File::open(infile)?))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(cd)
+            .env("PYTHONPATH", python_path(cd)?);
+        #[cfg(unix)]
+        set_limits(&mut command, limits)?;
+        let start = Instant::now();
+        let mut child = command.spawn()?;
+        let stdout = read_in_background(child.stdout.take());
+        let stderr = read_in_background(child.stderr.take());
+        let time_limit = Duration::from_secs_f64(limits.time_limit);
+        let mut result = wait_with_deadline(&mut child, start + time_limit)?;
+        result.elapsed = start.elapsed();
+        result.stdout = stdout.join().unwrap()?;
+        result.stderr = stderr.join().unwrap()?;
+        Ok(result)
+    }
+}
+
+fn read_in_background<R: Read + Send + 'static>(
+    pipe: Option<R>,
+) -> std::thread::JoinHandle<std::io::Result<Vec<u8>>> {
+    std::thread::spawn(move || {
+        let mut buf = vec![];
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buf)?;
+        }
+        Ok(buf)
+    })
+}
+
+// Restricts the address space of the child process.
+// The limit is doubled so that an MLE can be detected from the maximum RSS
+// instead of ending up as an allocation failure.
+// The stack is as large as the memory limit, as is the case in most judges.
+// On Linux, the child process is pinned to limits.cpu if given.
+#[cfg(unix)]
+fn set_limits(command: &mut Command, limits: &Limits) -> Result<()> {
+    use std::os::unix::process::CommandExt;
+    if let Some(cpu) = limits.cpu.filter(|&cpu| cpu >= MAX_CPUS) {
+        return Err(IOError::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "CPU {} is out of range (must be less than {})",
+                cpu, MAX_CPUS
+            ),
+        )
+        .into());
+    }
+    let bytes = (limits.memory_limit * 1024 * 1024) as libc::rlim_t;
+    let address_space = libc::rlimit {
+        rlim_cur: bytes.saturating_mul(2),
+        rlim_max: bytes.saturating_mul(2),
+    };
+    let stack = libc::rlimit {
+        rlim_cur: bytes,
+        rlim_max: bytes,
+    };
+    #[cfg(target_os = "linux")]
+    let cpu_set = limits.cpu.map(|cpu| {
+        // Safety: an all-zero cpu_set_t is an empty set.
+        let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        // Safety: cpu_set is a valid cpu_set_t.
+        unsafe { libc::CPU_SET(cpu, &mut cpu_set) };
+        cpu_set
+    });
+    // Safety: setrlimit and sched_setaffinity are async-signal-safe.
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_AS, &address_space) != 0
+                || libc::setrlimit(libc::RLIMIT_STACK, &stack) != 0
+            {
+                return Err(IOError::last_os_error());
+            }
+            #[cfg(target_os = "linux")]
+            if let Some(cpu_set) = cpu_set {
+                let size = std::mem::size_of::<libc::cpu_set_t>();
+                if libc::sched_setaffinity(0, size, &cpu_set) != 0 {
+                    return Err(IOError::last_os_error());
+                }
+            }
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+// Converts ru_maxrss to KiB. It is in KiB on Linux, but in bytes on macOS.
+#[cfg(unix)]
+fn max_rss_kib(ru_maxrss: libc::c_long) -> u64 {
+    let max_rss = ru_maxrss.max(0) as u64;
+    if cfg!(target_os = "macos") {
+        max_rss / 1024
+    } else {
+        max_rss
+    }
+}
+
+// Waits for the child, killing it if it is still running at the deadline.
+#[cfg(unix)]
+fn wait_with_deadline(child: &mut Child, deadline: Instant) -> Result<RunResult> {
+    let pid = child.id() as libc::pid_t;
+    let mut timed_out = false;
+    loop {
+        let mut status = 0;
+        // Safety: an all-zero rusage is a valid value.
+        let mut rusage: libc::rusage = unsafe { std::mem::zeroed() };
+        let options = if timed_out { 0 } else { libc::WNOHANG };
+        // Safety: pid is our child that is not reaped yet.
+        let ret = unsafe { libc::wait4(pid, &mut status, options, &mut rusage) };
+        if ret < 0 {
+            return Err(IOError::last_os_error().into());
+        }
+        if ret == pid {
+            let exited = libc::WIFEXITED(status);
+            return Ok(RunResult {
+                exit_code: exited.then(|| libc::WEXITSTATUS(status)),
+                signal: libc::WIFSIGNALED(status).then(|| libc::WTERMSIG(status)),
+                max_rss: Some(max_rss_kib(rusage.ru_maxrss)),
+                timed_out,
+                ..Default::default()
+            });
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            timed_out = true;
+        } else {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn wait_with_deadline(child: &mut Child, deadline: Instant) -> Result<RunResult> {
+    let mut timed_out = false;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(RunResult {
+                exit_code: status.code(),
+                timed_out,
+                ..Default::default()
+            });
+        }
+        if Instant::now() >= deadline && !timed_out {
+            child.kill()?;
+            timed_out = true;
+        } else {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
 }
 
 pub struct RunUtilImpl;
@@ -209,7 +513,7 @@ impl IoUtilExt for RunUtilImpl {}
 
 #[cfg(test)]
 mod tests {
-    use super::{RunUtil, RunUtilImpl};
+    use super::{Limits, RunResult, RunUtil, RunUtilImpl};
     use std::path::Path;
 
     #[test]
@@ -225,4 +529,45 @@ mod tests {
         let result = RunUtilImpl.build_command(&cmd, Path::new(infile), Path::new(outfile));
         assert_eq!(result, vec!["gcc", "-o", "out-file", "in-file"]);
     }
+
+    #[test]
+    fn limits_to_key_works() {
+        let limits = Limits {
+            time_limit: 2.0,
+            memory_limit: 1024,
+            cpu: None,
+        };
+        assert_eq!(limits.to_key(), "time_limit=2,memory_limit=1024");
+        let pinned = Limits {
+            cpu: Some(3),
+            ..limits.clone()
+        };
+        assert_eq!(pinned.to_key(), limits.to_key());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn max_rss_kib_works() {
+        let kib = if cfg!(target_os = "macos") { 2 } else { 2048 };
+        assert_eq!(super::max_rss_kib(2048), kib);
+        assert_eq!(super::max_rss_kib(-1), 0);
+    }
+
+    #[test]
+    fn run_result_exceeds_memory_works() {
+        let limits = Limits {
+            time_limit: 2.0,
+            memory_limit: 1,
+            cpu: None,
+        };
+        let mut result = RunResult {
+            max_rss: Some(1024),
+            ..Default::default()
+        };
+        assert!(!result.exceeds_memory(&limits));
+        result.max_rss = Some(1025);
+        assert!(result.exceeds_memory(&limits));
+        result.max_rss = None;
+        assert!(!result.exceeds_memory(&limits));
+    }
 }
@@ -0,0 +1,367 @@
+use pulldown_cmark::{html, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use std::collections::BTreeMap;
+
+/// A sample shown in a statement.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Sample {
+    pub input: String,
+    pub output: String,
+}
+
+/// Which file a sample code block in a statement shows.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SampleKind {
+    Input,
+    Output,
+}
+
+/// A code block in a statement marked as a sample, with ```` ```sample-input ```` or ```` ```sample-output ````.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SampleBlock {
+    pub kind: SampleKind,
+    /// 1-indexed line number in the statement of the first line of the content.
+    pub line: usize,
+    /// The content, where every line ends with '\n'.
+    pub content: String,
+}
+
+const SAMPLE_INPUT_INFO: &str = "sample-input";
+const SAMPLE_OUTPUT_INFO: &str = "sample-output";
+
+/// Finds the fenced code blocks marked as samples in a statement, in order of appearance.
+pub fn sample_blocks(markdown: &str) -> Vec<SampleBlock> {
+    let mut blocks = vec![];
+    // The fence that opened the current code block, and the block if it is a sample.
+    let mut open: Option<(String, Option<SampleBlock>)> = None;
+    for (index, line) in markdown.lines().enumerate() {
+        let trimmed = line.trim_start();
+        match open.take() {
+            None => {
+                let fence_char = match trimmed.chars().next() {
+                    Some(c @ ('`' | '~')) => c,
+                    _ => continue,
+                };
+                let fence_len = trimmed.chars().take_while(|&c| c == fence_char).count();
+                if fence_len < 3 {
+                    continue;
+                }
+                let info = trimmed[fence_len..].split_whitespace().next();
+                let kind = match info {
+                    Some(SAMPLE_INPUT_INFO) => Some(SampleKind::Input),
+                    Some(SAMPLE_OUTPUT_INFO) => Some(SampleKind::Output),
+                    _ => None,
+                };
+                let block = kind.map(|kind| SampleBlock {
+                    kind,
+                    line: index + 2,
+                    content: String::new(),
+                });
+                open = Some((trimmed[..fence_len].to_owned(), block));
+            }
+            Some((fence, block)) => {
+                let is_closing = trimmed.starts_with(&fence)
+                    && trimmed.trim_end().chars().all(|c| fence.starts_with(c));
+                if is_closing {
+                    blocks.extend(block);
+                    continue;
+                }
+                let block = block.map(|mut block| {
+                    block.content += line;
+                    block.content.push('\n');
+                    block
+                });
+                open = Some((fence, block));
+            }
+        }
+    }
+    blocks
+}
+
+/// What translations of a statement should have in common.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Outline {
+    /// Sections as (level, title, line).
+    pub headings: Vec<(HeadingLevel, String, usize)>,
+    /// Numbers in math, such as the bounds in constraints, as (number, line).
+    pub numbers: Vec<(String, usize)>,
+    pub samples: Vec<SampleBlock>,
+}
+
+/// Finds the sections, the numbers in math and the samples of a statement.
+pub fn outline(markdown: &str) -> Outline {
+    let line_of = |offset: usize| markdown[..offset].matches('\n').count() + 1;
+    let mut headings = vec![];
+    let mut numbers = vec![];
+    // The level, title and line of the heading being read.
+    let mut heading: Option<(HeadingLevel, String, usize)> = None;
+    for (event, range) in Parser::new_ext(markdown, Options::ENABLE_MATH).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                heading = Some((level, String::new(), line_of(range.start)));
+            }
+            Event::End(TagEnd::Heading(_)) => headings.extend(heading.take()),
+            Event::InlineMath(math) | Event::DisplayMath(math) => {
+                let line = line_of(range.start);
+                for number in math
+                    .split(|c: char| !c.is_ascii_digit() && c != '.')
+                    .map(|number| number.trim_matches('.'))
+                    .filter(|number| !number.is_empty())
+                {
+                    numbers.push((number.to_owned(), line));
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, title, _)) = &mut heading {
+                    *title += &text;
+                }
+            }
+            _ => {}
+        }
+    }
+    Outline {
+        headings,
+        numbers,
+        samples: sample_blocks(markdown),
+    }
+}
+
+/// Finds the differences between two translations of a statement, each given with its name.
+pub fn translation_differences(a: (&str, &Outline), b: (&str, &Outline)) -> Vec<String> {
+    let mut differences = vec![];
+    let ((a_name, a), (b_name, b)) = (a, b);
+
+    // Sections must be the same up to their titles.
+    let a_levels: Vec<HeadingLevel> = a.headings.iter().map(|&(level, _, _)| level).collect();
+    let b_levels: Vec<HeadingLevel> = b.headings.iter().map(|&(level, _, _)| level).collect();
+    if a_levels != b_levels {
+        let show = |name: &str, heading: Option<&(HeadingLevel, String, usize)>| match heading {
+            Some((level, title, line)) => {
+                format!(
+                    "{}:{} ({} {})",
+                    name,
+                    line,
+                    "#".repeat(*level as usize),
+                    title
+                )
+            }
+            None => format!("the end of {}", name),
+        };
+        let index = (0..).find(|&i| a_levels.get(i) != b_levels.get(i)).unwrap();
+        differences.push(format!(
+            "sections differ: {} vs {}",
+            show(a_name, a.headings.get(index)),
+            show(b_name, b.headings.get(index))
+        ));
+    }
+
+    // Numbers in math must be the same, but may appear in a different order.
+    fn count(outline: &Outline) -> BTreeMap<&str, Vec<usize>> {
+        let mut lines: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (number, line) in &outline.numbers {
+            lines.entry(number).or_default().push(*line);
+        }
+        lines
+    }
+    let (a_numbers, b_numbers) = (count(a), count(b));
+    for (name, ours, other_name, theirs) in [
+        (a_name, &a_numbers, b_name, &b_numbers),
+        (b_name, &b_numbers, a_name, &a_numbers),
+    ] {
+        for (number, lines) in ours {
+            let matched = theirs.get(number).map_or(0, |lines| lines.len());
+            for line in lines.iter().skip(matched) {
+                differences.push(format!(
+                    "{}:{}: {} appears in math fewer times in {}",
+                    name, line, number, other_name
+                ));
+            }
+        }
+    }
+
+    // Samples must be the same.
+    if a.samples.len() != b.samples.len() {
+        differences.push(format!(
+            "{} has {} sample blocks, but {} has {}",
+            a_name,
+            a.samples.len(),
+            b_name,
+            b.samples.len()
+        ));
+    }
+    for (a_block, b_block) in a.samples.iter().zip(&b.samples) {
+        if a_block.kind != b_block.kind || a_block.content != b_block.content {
+            differences.push(format!(
+                "samples differ: {}:{} vs {}:{}",
+                a_name, a_block.line, b_name, b_block.line
+            ));
+        }
+    }
+    differences
+}
+
+// Headings of a statement in the language named by the statement's file name (e.g. ja.md).
+struct Labels {
+    time_limit: &'static str,
+    memory_limit: &'static str,
+    sample_input: &'static str,
+    sample_output: &'static str,
+}
+
+fn labels(lang: &str) -> Labels {
+    match lang {
+        "ja" => Labels {
+            time_limit: "実行時間制限",
+            memory_limit: "メモリ制限",
+            sample_input: "入力例",
+            sample_output: "出力例",
+        },
+        _ => Labels {
+            time_limit: "Time Limit",
+            memory_limit: "Memory Limit",
+            sample_input: "Sample Input",
+            sample_output: "Sample Output",
+        },
+    }
+}
+
+// KaTeX renders the math that pulldown-cmark puts in <span class="math ...">.
+const KATEX_VERSION: &str = "0.16.11";
+const RENDER_MATH: &str = r#"document.addEventListener("DOMContentLoaded", () => {
+  for (const e of document.querySelectorAll(".math")) {
+    katex.render(e.textContent, e, { displayMode: e.classList.contains("math-display"), throwOnError: false });
+  }
+});"#;
+
+/// Renders a statement written in Markdown into standalone HTML.
+/// `$...$` and `$$...$$` are rendered as math by KaTeX.
+/// The limits are shown above the statement, and the samples below it.
+pub fn render(
+    markdown: &str,
+    lang: &str,
+    time_limit: f64,
+    memory_limit: u64,
+    samples: &[Sample],
+) -> String {
+    let labels = labels(lang);
+    let mut body = format!(
+        "<p>{}: {} sec / {}: {} MiB</p>\n",
+        labels.time_limit, time_limit, labels.memory_limit, memory_limit
+    );
+    let options = Options::ENABLE_MATH | Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    html::push_html(&mut body, Parser::new_ext(markdown, options));
+    for (i, sample) in samples.iter().enumerate() {
+        for (label, content) in [
+            (labels.sample_input, &sample.input),
+            (labels.sample_output, &sample.output),
+        ] {
+            body += &format!(
+                "<h3>{} {}</h3>\n<pre>{}</pre>\n",
+                label,
+                i + 1,
+                escape(content)
+            );
+        }
+    }
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="{lang}">
+<head>
+<meta charset="utf-8">
+<link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/katex@{katex}/dist/katex.min.css">
+<script defer src="https://cdn.jsdelivr.net/npm/katex@{katex}/dist/katex.min.js"></script>
+<script>
+{render_math}
+</script>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        lang = escape(lang),
+        katex = KATEX_VERSION,
+        render_math = RENDER_MATH,
+        body = body,
+    )
+}
+
+// Escapes text to be put in HTML.
+fn escape(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_works() {
+        let samples = [Sample {
+            input: "1 <2\n".to_owned(),
+            output: "3\n".to_owned(),
+        }];
+        let html = render("# 問題\n$a_i * b_i$ を求めよ。", "ja", 2.0, 1024, &samples);
+        assert!(html.contains("<p>実行時間制限: 2 sec / メモリ制限: 1024 MiB</p>"));
+        assert!(html.contains("<h1>問題</h1>"));
+        assert!(html.contains(r#"<span class="math math-inline">a_i * b_i</span>"#));
+        assert!(html.contains("<h3>入力例 1</h3>\n<pre>1 &lt;2\n</pre>"));
+        assert!(html.contains("<h3>出力例 1</h3>\n<pre>3\n</pre>"));
+    }
+
+    #[test]
+    fn sample_blocks_works() {
+        let markdown = "## 入力例 1\n```sample-input\n1 2\n```\n\n```cpp\nint main() {}\n```\n\n````sample-output\n3\n```\n````\n";
+        assert_eq!(
+            sample_blocks(markdown),
+            vec![
+                SampleBlock {
+                    kind: SampleKind::Input,
+                    line: 3,
+                    content: "1 2\n".to_owned(),
+                },
+                SampleBlock {
+                    kind: SampleKind::Output,
+                    line: 11,
+                    content: "3\n```\n".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn translation_differences_works() {
+        let ja = outline("# 問題\n## 制約\n- $1 \\leq N \\leq 2 \\times 10^5$\n## 入力例 1\n```sample-input\n1\n```\n");
+        let en = outline("# Problem\n## Constraints\n- $1 \\leq N \\leq 2 \\times 10^5$\n## Sample Input 1\n```sample-input\n1\n```\n");
+        assert_eq!(ja.headings.len(), 3);
+        assert_eq!(ja.headings[1].1, "制約");
+        assert_eq!(ja.numbers.len(), 4);
+        assert!(translation_differences(("ja.md", &ja), ("en.md", &en)).is_empty());
+
+        let en = outline("# Problem\n### Constraints\n- $1 \\leq N \\leq 10^5$\n## Sample Input 1\n```sample-input\n2\n```\n");
+        assert_eq!(
+            translation_differences(("ja.md", &ja), ("en.md", &en)),
+            vec![
+                "sections differ: ja.md:2 (## 制約) vs en.md:2 (### Constraints)".to_owned(),
+                "ja.md:3: 2 appears in math fewer times in en.md".to_owned(),
+                "samples differ: ja.md:6 vs en.md:6".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_uses_english_labels_for_other_languages() {
+        let html = render("", "en", 1.5, 256, &[]);
+        assert!(html.contains("<p>Time Limit: 1.5 sec / Memory Limit: 256 MiB</p>"));
+        assert!(!html.contains("Sample Input"));
+    }
+}
@@ -0,0 +1,188 @@
+use crate::entity::config::JudgeLimitsConfig;
+
+/// Sizes and recorded judging times of a set of testcases.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct TestsetStats {
+    /// Number of testcases.
+    pub tests: usize,
+    /// Total size of the inputs in bytes.
+    pub input_size: u64,
+    /// Size and name of the largest input.
+    pub largest_input: Option<(u64, String)>,
+    /// Total size of the outputs in bytes.
+    pub output_size: u64,
+    /// Size and name of the largest output.
+    pub largest_output: Option<(u64, String)>,
+    /// Total recorded time in seconds, of the testcases whose time is recorded.
+    pub time: f64,
+    /// Recorded time and name of the slowest testcase.
+    pub slowest: Option<(f64, String)>,
+    /// Number of testcases whose time is not recorded.
+    pub untimed: usize,
+}
+
+impl TestsetStats {
+    /// Adds a testcase. `time` is None if it is not recorded.
+    pub fn add(&mut self, name: &str, input_size: u64, output_size: u64, time: Option<f64>) {
+        self.tests += 1;
+        self.input_size += input_size;
+        if self
+            .largest_input
+            .as_ref()
+            .map_or(true, |&(size, _)| input_size > size)
+        {
+            self.largest_input = Some((input_size, name.to_owned()));
+        }
+        self.output_size += output_size;
+        if self
+            .largest_output
+            .as_ref()
+            .map_or(true, |&(size, _)| output_size > size)
+        {
+            self.largest_output = Some((output_size, name.to_owned()));
+        }
+        match time {
+            Some(time) => {
+                self.time += time;
+                if self
+                    .slowest
+                    .as_ref()
+                    .map_or(true, |&(slowest, _)| time > slowest)
+                {
+                    self.slowest = Some((time, name.to_owned()));
+                }
+            }
+            None => self.untimed += 1,
+        }
+    }
+
+    /// One-line summary, e.g. "3 tests, input 1.5 KiB, output 12 B, time 0.031 s".
+    pub fn summary(&self) -> String {
+        let time = if self.untimed == self.tests {
+            "time unknown".to_owned()
+        } else if self.untimed > 0 {
+            format!("time {:.3} s + {} untimed", self.time, self.untimed)
+        } else {
+            format!("time {:.3} s", self.time)
+        };
+        format!(
+            "{} tests, input {}, output {}, {}",
+            self.tests,
+            format_size(self.input_size),
+            format_size(self.output_size),
+            time
+        )
+    }
+}
+
+/// Compares the totals of a testset with the limits of the judge.
+/// Returns a description of each limit that is exceeded.
+pub fn judge_limit_warnings(total: &TestsetStats, limits: &JudgeLimitsConfig) -> Vec<String> {
+    const MIB: f64 = 1024.0 * 1024.0;
+    let checks = [
+        (
+            "the number of tests",
+            total.tests as f64,
+            "max_tests",
+            limits.max_tests.map(|x| x as f64),
+            "",
+        ),
+        (
+            "the total input size",
+            total.input_size as f64 / MIB,
+            "max_total_input_size",
+            limits.max_total_input_size,
+            " MiB",
+        ),
+        (
+            "the total output size",
+            total.output_size as f64 / MIB,
+            "max_total_output_size",
+            limits.max_total_output_size,
+            " MiB",
+        ),
+        (
+            "the judging time",
+            total.time,
+            "max_judging_time",
+            limits.max_judging_time,
+            " s",
+        ),
+    ];
+    let mut warnings = vec![];
+    for (title, value, name, limit, unit) in checks {
+        if let Some(limit) = limit {
+            if value > limit {
+                let value = (value * 1000.0).round() / 1000.0;
+                warnings.push(format!(
+                    "{} ({}{}) exceeds {} = {}{}",
+                    title, value, unit, name, limit, unit
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+/// Formats a size in bytes, e.g. "12 B", "1.5 KiB" or "3.0 MiB".
+pub fn format_size(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = 1024 * 1024;
+    if bytes < KIB {
+        format!("{} B", bytes)
+    } else if bytes < MIB {
+        format!("{:.1} KiB", bytes as f64 / KIB as f64)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / MIB as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn testset_stats_work() {
+        let mut stats = TestsetStats::default();
+        stats.add("1.txt", 10, 2, Some(0.5));
+        stats.add("2.txt", 2000, 1, None);
+        stats.add("3.txt", 5, 4, Some(0.25));
+        assert_eq!(stats.tests, 3);
+        assert_eq!(stats.input_size, 2015);
+        assert_eq!(stats.largest_input, Some((2000, "2.txt".to_owned())));
+        assert_eq!(stats.largest_output, Some((4, "3.txt".to_owned())));
+        assert_eq!(stats.slowest, Some((0.5, "1.txt".to_owned())));
+        assert_eq!(stats.untimed, 1);
+        assert_eq!(
+            stats.summary(),
+            "3 tests, input 2.0 KiB, output 7 B, time 0.750 s + 1 untimed"
+        );
+    }
+
+    #[test]
+    fn judge_limit_warnings_works() {
+        let mut stats = TestsetStats::default();
+        stats.add("1.txt", 3 * 1024 * 1024, 2, Some(1.5));
+        let limits = JudgeLimitsConfig {
+            max_tests: Some(1),
+            max_total_input_size: Some(2.0),
+            max_total_output_size: None,
+            max_judging_time: Some(1.0),
+        };
+        assert_eq!(
+            judge_limit_warnings(&stats, &limits),
+            vec![
+                "the total input size (3 MiB) exceeds max_total_input_size = 2 MiB",
+                "the judging time (1.5 s) exceeds max_judging_time = 1 s",
+            ]
+        );
+        assert!(judge_limit_warnings(&stats, &JudgeLimitsConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn format_size_works() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+/// Which bounds of a variable were reached, as written in a test overview log of a testlib validator.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct BoundsHit {
+    /// Was the variable ever at its minimum?
+    pub min: bool,
+    /// Was the variable ever at its maximum?
+    pub max: bool,
+}
+
+impl BoundsHit {
+    /// Combines bounds hit by two sets of inputs.
+    pub fn merge(self, other: BoundsHit) -> BoundsHit {
+        BoundsHit {
+            min: self.min || other.min,
+            max: self.max || other.max,
+        }
+    }
+}
+
+/// Reads a test overview log written by a testlib validator run with `--testOverviewLogFileName`.
+/// Each variable is on a line of the form `"n": min-value-hit max-value-hit`, where the words are present
+/// only if the bound was hit. Other lines, such as features, are ignored.
+pub fn parse_overview_log(log: &str) -> BTreeMap<String, BoundsHit> {
+    let mut result = BTreeMap::new();
+    for line in log.lines() {
+        let Some(rest) = line.strip_prefix('"') else {
+            continue;
+        };
+        let Some((variable, words)) = rest.split_once("\":") else {
+            continue;
+        };
+        let mut hit = BoundsHit::default();
+        for word in words.split_whitespace() {
+            match word {
+                "min-value-hit" => hit.min = true,
+                "max-value-hit" => hit.max = true,
+                _ => {}
+            }
+        }
+        let entry: &mut BoundsHit = result.entry(variable.to_owned()).or_default();
+        *entry = entry.merge(hit);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_overview_log_works() {
+        let log = "\"a[i]\": min-value-hit\n\"n\":\nfeature \"sorted\": hit\n\"q\": min-value-hit max-value-hit\n";
+        let result = parse_overview_log(log);
+        assert_eq!(result.len(), 3);
+        assert_eq!(
+            result["a[i]"],
+            BoundsHit {
+                min: true,
+                max: false
+            }
+        );
+        assert_eq!(result["n"], BoundsHit::default());
+        assert_eq!(
+            result["q"],
+            BoundsHit {
+                min: true,
+                max: true
+            }
+        );
+    }
+}
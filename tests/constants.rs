@@ -0,0 +1,41 @@
+use assert_cmd::cmd::Command;
+use assert_fs::prelude::*;
+
+const CONFIG: &str = r#"
+[[generators]]
+language_name = "Python"
+path = "gen.py"
+
+[[languages]]
+language_name = "Python"
+target_ext = "py"
+compile = ["cp", "$IN", "$OUT"]
+run = ["python3", "$OUT"]
+"#;
+
+#[test]
+fn python_sources_import_constants() {
+    // Asserts that a generator can `import constants`, and that it sees a changed value.
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("in").create_dir_all().unwrap();
+    temp.child("gen.py")
+        .write_str("import constants\nopen('in/1.txt', 'w').write(f'{constants.N_MAX} {constants.YES}\\n')\n")
+        .unwrap();
+    for n_max in [7, 8] {
+        temp.child("creo.toml")
+            .write_str(&format!(
+                "[constants]\nN_MAX = {}\nYES = \"Yes\"\n{}",
+                n_max, CONFIG
+            ))
+            .unwrap();
+        Command::cargo_bin("creo")
+            .unwrap()
+            .current_dir(&temp)
+            .args(["gen", "."])
+            .unwrap();
+        temp.child("in/1.txt")
+            .assert(format!("{} Yes\n", n_max).as_str());
+    }
+
+    temp.close().unwrap();
+}